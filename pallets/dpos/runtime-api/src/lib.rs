@@ -0,0 +1,31 @@
+//! Runtime API definition for the DPoS pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Preview of the active validator set of the next epoch
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, PartialEq, Eq, Clone)]
+pub struct ValidatorSetPreview<AccountId, Balance> {
+	/// The predicted winners in the form of `(candidate, bond, total)`
+	pub winners: Vec<(AccountId, Balance, Balance)>,
+	/// The online candidates that are not predicted to win in the form of `(candidate, total,
+	/// gap)`, where `gap` is the stake the candidate is missing to reach the cut-off, the lowest
+	/// total of the winners
+	pub losers: Vec<(AccountId, Balance, Balance)>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait DposApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Runs the election of the next epoch against the current state without writing to
+		/// storage
+		fn preview_next_validator_set() -> ValidatorSetPreview<AccountId, Balance>;
+	}
+}
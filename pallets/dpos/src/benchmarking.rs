@@ -2,24 +2,308 @@
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
+use crate::types::*;
 #[allow(unused)]
 use crate::Pallet as Dpos;
-use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame::deps::{
+	frame_benchmarking::v2::*,
+	frame_support::{
+		traits::{
+			fungible::{Inspect, Mutate},
+			EnsureOrigin, Get,
+		},
+		weights::Weight,
+	},
+	frame_system::RawOrigin,
+};
+use sp_runtime::{
+	traits::{Bounded, Zero},
+	BoundedVec, FixedU128, Perbill, Saturating,
+};
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+/// A funded account that can cover a candidate bond or a delegation
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	let balance = T::MinCandidateBond::get()
+		.max(T::MinDelegateAmount::get())
+		.saturating_mul(1_000u32.into())
+		.saturating_add(T::NativeBalance::minimum_balance());
+	T::NativeBalance::set_balance(&who, balance);
+	who
+}
+
+/// Registers `count` candidates with the minimum bond
+fn register_candidates<T: Config>(count: u32) -> Vec<T::AccountId> {
+	(0..count)
+		.map(|index| {
+			let candidate = funded_account::<T>("candidate", index);
+			Pallet::<T>::register_as_candidate_inner(&candidate, T::MinCandidateBond::get())
+				.expect("Failed to register the candidate");
+			candidate
+		})
+		.collect()
+}
+
+/// Registers `count` candidates with the minimum bond and stores them as the electable
+/// candidates read by the paged election
+fn seed_electable_candidates<T: Config>(count: u32) -> Vec<T::AccountId> {
+	let candidates = register_candidates::<T>(count);
+	let electable_candidates = candidates
+		.iter()
+		.map(|candidate| {
+			(candidate.clone(), T::MinCandidateBond::get(), T::MinCandidateBond::get())
+		})
+		.collect::<Vec<_>>();
+	ElectableCandidates::<T>::put(
+		BoundedVec::try_from(electable_candidates).expect("Too many candidates"),
+	);
+	candidates
+}
+
+/// Seeds `count` delegations to the candidate
+fn seed_candidate_delegations<T: Config>(candidate: &T::AccountId, count: u32) {
+	let amount = T::MinDelegateAmount::get();
+	let delegators = (0..count)
+		.map(|index| {
+			let delegator: T::AccountId = account("delegator", index, SEED);
+			DelegationInfos::<T>::insert(&delegator, candidate, DelegationInfo::default(amount));
+			delegator
+		})
+		.collect::<Vec<_>>();
+	CandidateDelegators::<T>::insert(
+		candidate,
+		BoundedVec::try_from(delegators).expect("Too many delegators"),
+	);
+}
+
+/// Funds the `RewardSource` so that the rewards can be paid from it
+fn fund_reward_source<T: Config>() {
+	let source = match T::RewardSource::get() {
+		RewardSource::Mint => return,
+		RewardSource::Pot => Pallet::<T>::reward_pot_account(),
+		RewardSource::Treasury(treasury) => treasury,
+	};
+	T::NativeBalance::set_balance(&source, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Seeds `count` delegations of the delegator with unclaimed rewards, spread over the validators
+/// it can delegate to in every epoch kept in the history
+fn seed_delegator_rewards<T: Config>(delegator: &T::AccountId, count: u32) {
+	let max_delegate_count = T::MaxDelegateCount::get().max(1);
+	let amount = T::MinDelegateAmount::get();
+	for index in 0..count {
+		let epoch_index = index / max_delegate_count;
+		let validator: T::AccountId = account("validator", index % max_delegate_count, SEED);
+		let mut reward_pool = ValidatorRewardPool::<T>::new(amount, amount, Perbill::zero());
+		reward_pool.accrue(FixedU128::from_u32(1));
+		ValidatorRewardPools::<T>::insert(epoch_index, &validator, reward_pool);
+		EpochDelegations::<T>::insert((epoch_index, delegator, &validator), amount);
+	}
+}
+
+/// Seeds the reward points of the account, enough to be paid into a new account
+fn seed_reward_points<T: Config>(who: &T::AccountId, earned_epoch: u32) {
+	RewardPoints::<T>::insert(
+		who,
+		T::NativeBalance::minimum_balance().saturating_mul(10u32.into()),
+	);
+	RewardLastEarned::<T>::insert(who, earned_epoch);
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
+	fn elect_validators(
+		c: Linear<1, { T::MaxElectableCandidates::get() }>,
+		d: Linear<0, { T::MaxCandidateDelegators::get() }>,
+	) {
+		let candidates = seed_electable_candidates::<T>(c);
+		let amount = T::MinDelegateAmount::get();
+		let delegations = (0..d)
+			.map(|index| (account("delegator", index, SEED), amount))
+			.collect::<Vec<_>>();
+		ElectableCandidateDelegations::<T>::insert(
+			&candidates[0],
+			BoundedVec::try_from(delegations).expect("Too many delegators"),
+		);
+		PagedElectionPhase::<T>::put(ElectionPhase::Elect);
+
+		#[block]
+		{
+			Pallet::<T>::process_election_page();
+		}
+
+		assert_eq!(PagedElectionPhase::<T>::get(), None);
+		assert_eq!(ElectableCandidateDelegations::<T>::iter().count(), 0);
+	}
+
+	#[benchmark]
+	fn read_electable_candidates(c: Linear<1, { T::SnapshotPageSize::get() }>) {
+		register_candidates::<T>(c);
+		PagedElectionPhase::<T>::put(ElectionPhase::Candidates { bag: 0, position: 0 });
+
+		#[block]
+		{
+			Pallet::<T>::process_election_page();
+		}
+
+		assert_ne!(
+			PagedElectionPhase::<T>::get(),
+			Some(ElectionPhase::Candidates { bag: 0, position: 0 })
+		);
+	}
+
+	#[benchmark]
+	fn read_candidate_delegations(
+		c: Linear<1, { T::SnapshotPageSize::get() }>,
+		d: Linear<0, { T::MaxCandidateDelegators::get() }>,
+	) {
+		let candidates = seed_electable_candidates::<T>(c);
+		seed_candidate_delegations::<T>(&candidates[0], d);
+		PagedElectionPhase::<T>::put(ElectionPhase::Delegations { cursor: 0 });
+
+		#[block]
+		{
+			Pallet::<T>::process_election_page();
+		}
+
+		assert_eq!(ElectableCandidateDelegations::<T>::get(&candidates[0]).len() as u32, d);
+	}
+
+	#[benchmark]
+	fn capture_snapshot_page(
+		v: Linear<1, { T::SnapshotPageSize::get() }>,
+		d: Linear<0, { T::MaxCandidateDelegators::get() }>,
+	) {
+		let candidates = register_candidates::<T>(v);
+		seed_candidate_delegations::<T>(&candidates[0], d);
+		let pending_validator_set = candidates
+			.into_iter()
+			.map(|candidate| (candidate, T::MinCandidateBond::get(), T::MinCandidateBond::get()))
+			.collect::<Vec<_>>();
+		PendingValidatorSet::<T>::put(
+			BoundedVec::try_from(pending_validator_set).expect("Too many validators"),
+		);
+
+		#[block]
+		{
+			Pallet::<T>::capture_next_snapshot_page();
+		}
+
+		assert_eq!(SnapshotPageCursor::<T>::get(), v);
+	}
+
+	#[benchmark]
+	fn set_inflation_curve() -> Result<(), BenchmarkError> {
+		let origin = T::ConfigControllerOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let curve = InflationCurve {
+			ideal_stake: Perbill::from_percent(50),
+			min_inflation: Perbill::from_percent(2),
+			max_inflation: Perbill::from_percent(10),
+			falloff: Perbill::from_percent(5),
+		};
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(curve));
+
+		assert_eq!(ConfiguredInflationCurve::<T>::get(), Some(curve));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn payout_rewards(
+		n: Linear<1, { T::MaxPayoutBatch::get() }>,
+		d: Linear<0, { Pallet::<T>::max_delegator_reward_claims() }>,
+	) {
+		fund_reward_source::<T>();
+		EpochIndex::<T>::put(T::HistoryDepth::get());
+		let accounts = (0..n)
+			.map(|index| {
+				let who = funded_account::<T>("payee", index);
+				seed_reward_points::<T>(&who, 0);
+				who
+			})
+			.collect::<Vec<_>>();
+		seed_delegator_rewards::<T>(&accounts[0], d);
 		let caller: T::AccountId = whitelisted_caller();
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		_(RawOrigin::Signed(caller), BoundedVec::try_from(accounts.clone()).unwrap());
+
+		assert!(accounts.iter().all(|who| RewardPoints::<T>::get(who).is_zero()));
+	}
+
+	#[benchmark]
+	fn sweep_expired_rewards(n: Linear<0, 1_000>) {
+		fund_reward_source::<T>();
+		EpochIndex::<T>::put(T::RewardExpiryEpochs::get());
+		let accounts = (0..n)
+			.map(|index| {
+				let who = funded_account::<T>("payee", index);
+				seed_reward_points::<T>(&who, 0);
+				who
+			})
+			.collect::<Vec<_>>();
+
+		#[block]
+		{
+			Pallet::<T>::sweep_expired_rewards(Weight::MAX);
+		}
+
+		if !T::RewardExpiryEpochs::get().is_zero() {
+			assert!(accounts.iter().all(|who| RewardPoints::<T>::get(who).is_zero()));
+		}
+	}
+
+	#[benchmark]
+	fn claim_reward(d: Linear<0, { Pallet::<T>::max_delegator_reward_claims() }>) {
+		fund_reward_source::<T>();
+		EpochIndex::<T>::put(T::HistoryDepth::get());
+		let claimer = funded_account::<T>("claimer", 0);
+		seed_reward_points::<T>(&claimer, 0);
+		seed_delegator_rewards::<T>(&claimer, d);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(claimer.clone()));
+
+		assert!(RewardPoints::<T>::get(&claimer).is_zero());
+	}
+
+	#[benchmark]
+	fn settle_pruned_rewards(d: Linear<0, 1_000>, v: Linear<0, { T::MaxActiveValidators::get() }>) {
+		// The epoch 0 is pruned from the history
+		EpochIndex::<T>::put(T::HistoryDepth::get().saturating_add(1));
+		let amount = T::MinDelegateAmount::get();
+		for index in 0..v {
+			let validator: T::AccountId = account("validator", index, SEED);
+			let mut reward_pool = ValidatorRewardPool::<T>::new(amount, amount, Perbill::zero());
+			reward_pool.accrue(FixedU128::from_u32(1));
+			ValidatorRewardPools::<T>::insert(0, &validator, reward_pool);
+		}
+		for index in 0..d {
+			let delegator: T::AccountId = account("delegator", index, SEED);
+			let validator: T::AccountId = account("validator", index % v.max(1), SEED);
+			EpochDelegations::<T>::insert((0, &delegator, &validator), amount);
+		}
+
+		#[block]
+		{
+			Pallet::<T>::settle_pruned_rewards(Weight::MAX);
+		}
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(OldestUnsettledEpoch::<T>::get(), 1);
+		assert_eq!(EpochDelegations::<T>::iter_prefix((0,)).count(), 0);
 	}
 
-	impl_benchmark_test_suite!(Dpos, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(
+		Dpos,
+		crate::mock::TestExtBuilder::default().build(),
+		crate::mock::Test
+	);
 }
@@ -1,7 +1,7 @@
 pub type Balance = u128;
 pub type AccountId = u64;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct TestAccount {
 	pub id: AccountId,
 	pub balance: u128,
@@ -15,6 +15,7 @@ impl TestAccount {
 
 pub const TEST_BLOCKS_PER_EPOCH: u64 = 20;
 pub const HALF_EPOCH: u64 = TEST_BLOCKS_PER_EPOCH / 2;
+pub const TEST_BLOCK_TIME: u64 = 6_000;
 pub const TEN_THOUSAND_BALANCE: u128 = 10_0000;
 
 pub const ACCOUNT_1: TestAccount = TestAccount { id: 1, balance: 10 };
@@ -0,0 +1,169 @@
+//! Election algorithms that can be plugged into the pallet through the `ElectionProvider` config
+//! type.
+
+use crate::{BalanceOf, Config, ElectionProvider, EpochIndex};
+use frame::deps::frame_support::traits::Get;
+use sp_npos_elections::{
+	assignment_ratio_to_staked_normalized, seq_phragmen, to_supports, BalancingConfig,
+	ElectionResult, IdentifierT, VoteWeight,
+};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, SaturatedConversion, Zero},
+	Perbill,
+};
+use sp_std::{cmp::Reverse, collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+/// Selects the top candidates sorted by their total stake (bond + total delegations). This is the
+/// default election of the pallet.
+pub struct TopStakeElection;
+
+impl<AccountId, Balance: Ord + Copy> ElectionProvider<AccountId, Balance> for TopStakeElection {
+	fn elect(
+		to_elect: u32,
+		mut candidates: Vec<(AccountId, Balance, Balance)>,
+		_edges: Vec<(AccountId, AccountId, Balance)>,
+	) -> Vec<(AccountId, Balance, Balance)> {
+		// Sort candidates by their total stake in descending order
+		candidates.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+
+		// Select the top candidates based on the maximum active validators allowed
+		candidates.into_iter().take(to_elect as usize).collect()
+	}
+
+	fn needs_edges() -> bool {
+		false
+	}
+}
+
+/// Elects the winners using the sequential Phragmén method. Each delegator votes with its total
+/// delegated amount for every candidate it delegated to, and each candidate votes for itself with
+/// its bond. The winners are returned with the backing assigned to them, sorted in descending
+/// order.
+///
+/// If the election fails, it falls back to the [`TopStakeElection`].
+pub struct SequentialPhragmenElection;
+
+impl<AccountId: IdentifierT, Balance: AtLeast32BitUnsigned + Copy>
+	ElectionProvider<AccountId, Balance> for SequentialPhragmenElection
+{
+	fn elect(
+		to_elect: u32,
+		candidates: Vec<(AccountId, Balance, Balance)>,
+		edges: Vec<(AccountId, AccountId, Balance)>,
+	) -> Vec<(AccountId, Balance, Balance)> {
+		// Collect the budget and the approved candidates of every voter
+		let mut voter_budgets: BTreeMap<AccountId, Balance> = BTreeMap::new();
+		let mut voter_targets: BTreeMap<AccountId, Vec<AccountId>> = BTreeMap::new();
+		let mut add_vote = |voter: AccountId, target: AccountId, stake: Balance| {
+			let budget = voter_budgets.entry(voter.clone()).or_insert(Zero::zero());
+			*budget = budget.saturating_add(stake);
+			voter_targets.entry(voter).or_default().push(target);
+		};
+		let candidate_bonds: BTreeMap<AccountId, Balance> = candidates
+			.iter()
+			.map(|(candidate, bond, _)| (candidate.clone(), *bond))
+			.collect();
+		for (candidate, bond) in candidate_bonds.iter() {
+			add_vote(candidate.clone(), candidate.clone(), *bond);
+		}
+		for (delegator, candidate, amount) in edges {
+			if candidate_bonds.contains_key(&candidate) {
+				add_vote(delegator, candidate, amount);
+			}
+		}
+
+		// Vote weights are bounded to `u64`, the stake is scaled down by the total stake to fit
+		// into a vote weight
+		let total_stake = voter_budgets
+			.values()
+			.fold(Balance::zero(), |total, budget| total.saturating_add(*budget));
+		let max_vote_weight: Balance = VoteWeight::MAX.saturated_into();
+		let factor = (total_stake / max_vote_weight).max(Balance::one());
+		let stake_of = |who: &AccountId| -> VoteWeight {
+			voter_budgets
+				.get(who)
+				.map(|budget| (*budget / factor).saturated_into())
+				.unwrap_or(0)
+		};
+
+		let election_result = seq_phragmen::<AccountId, Perbill>(
+			to_elect as usize,
+			candidate_bonds.keys().cloned().collect(),
+			voter_targets
+				.into_iter()
+				.map(|(who, targets)| {
+					let budget = stake_of(&who);
+					(who, budget, targets)
+				})
+				.collect(),
+			Some(BalancingConfig { iterations: 10, tolerance: 0 }),
+		);
+		let supports = match election_result.and_then(|ElectionResult { assignments, .. }| {
+			assignment_ratio_to_staked_normalized(assignments, stake_of)
+		}) {
+			Ok(staked_assignments) => to_supports(&staked_assignments),
+			Err(_) => return TopStakeElection::elect(to_elect, candidates, Vec::new()),
+		};
+
+		let mut winners: Vec<(AccountId, Balance, Balance)> = supports
+			.into_iter()
+			.filter_map(|(winner, support)| {
+				let bond = candidate_bonds.get(&winner)?;
+				let backing = Balance::saturated_from(support.total).saturating_mul(factor);
+				Some((winner, *bond, backing))
+			})
+			.collect();
+		winners.sort_by_key(|&(_, _, backing)| Reverse(backing));
+		winners
+	}
+}
+
+/// Ranks the candidates by their total stake, the account id breaking the ties, and rotates
+/// `RotationSlots` of the active slots among the runners-up every epoch.
+///
+/// The top `to_elect - RotationSlots` candidates always keep their slots. The remaining slots are
+/// given to a window of the runners-up that moves forward by `RotationSlots` every epoch, so that
+/// mid-sized candidates also take part in the block production.
+pub struct StakeWeightRotationElection<T, RotationSlots>(PhantomData<(T, RotationSlots)>);
+
+impl<T: Config, RotationSlots: Get<u32>> ElectionProvider<T::AccountId, BalanceOf<T>>
+	for StakeWeightRotationElection<T, RotationSlots>
+{
+	fn elect(
+		to_elect: u32,
+		candidates: Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)>,
+		_edges: Vec<(T::AccountId, T::AccountId, BalanceOf<T>)>,
+	) -> Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)> {
+		// Rank the candidates by their total stake in descending order, ties are broken by the
+		// account id so that the ranking does not depend on the order of the candidates
+		let mut ranked_candidates = candidates;
+		ranked_candidates.sort_by(|(a_id, _, a_stake), (b_id, _, b_stake)| {
+			b_stake.cmp(a_stake).then_with(|| a_id.cmp(b_id))
+		});
+
+		let to_elect = to_elect as usize;
+		if ranked_candidates.len() <= to_elect {
+			return ranked_candidates;
+		}
+
+		// The top ranked candidates keep their slots, the rest of the slots rotate among the
+		// runners-up
+		let rotation_slots = (RotationSlots::get() as usize).min(to_elect);
+		let mut runners_up = ranked_candidates.split_off(to_elect - rotation_slots);
+		let mut winners = ranked_candidates;
+
+		let offset =
+			(EpochIndex::<T>::get() as usize).saturating_mul(rotation_slots) % runners_up.len();
+		runners_up.rotate_left(offset);
+		winners.extend(runners_up.into_iter().take(rotation_slots));
+
+		winners.sort_by(|(a_id, _, a_stake), (b_id, _, b_stake)| {
+			b_stake.cmp(a_stake).then_with(|| a_id.cmp(b_id))
+		});
+		winners
+	}
+
+	fn needs_edges() -> bool {
+		false
+	}
+}
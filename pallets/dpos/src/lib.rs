@@ -35,9 +35,13 @@
 
 pub use pallet::*;
 
+pub mod election;
+pub mod migrations;
 pub mod types;
 pub mod weights;
 
+pub use election::{SequentialPhragmenElection, StakeWeightRotationElection, TopStakeElection};
+
 #[cfg(test)]
 mod mock;
 
@@ -58,38 +62,66 @@ pub mod pallet {
 			dispatch::DispatchResult,
 			pallet_prelude::{ValueQuery, *},
 			sp_runtime::traits::{CheckedAdd, CheckedSub, Zero},
+			storage::with_storage_layer,
 			traits::{
 				fungible::{self, Mutate, MutateHold},
-				tokens::{Fortitude, Precision},
-				FindAuthor,
+				tokens::{Fortitude, Precision, Preservation},
+				FindAuthor, Time, ValidatorRegistration,
 			},
-			Twox64Concat,
+			PalletId, Twox64Concat,
 		},
 		frame_system::pallet_prelude::{OriginFor, *},
 	};
-	use sp_runtime::{traits::One, BoundedVec, Percent, Saturating};
-	use sp_std::{
-		cmp::Reverse,
-		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
-		prelude::*,
-		vec::Vec,
+	use sp_runtime::{
+		traits::{AccountIdConversion, Bounded, Convert, One, UniqueSaturatedInto},
+		BoundedVec, FixedU128, Perbill, Saturating,
 	};
+	use sp_std::{cmp::Reverse, collections::btree_set::BTreeSet, prelude::*, vec::Vec};
 
 	/// Reporting a new set of validators to an external system or component.
 	pub trait ReportNewValidatorSet<AccountId> {
 		fn report_new_validator_set(_new_set: Vec<AccountId>) {}
 	}
 
+	/// No external system is reported, for example when `pallet_session` receives the validator
+	/// set through the `SessionManager` implementation of the pallet
+	impl<AccountId> ReportNewValidatorSet<AccountId> for () {}
+
 	/// A hook to act on if there is a validator in the active validator set misbehaves
 	pub trait OnSlashHandler<AccountId, Balance> {
 		fn on_slash(_who: &AccountId, _amount: Balance) {}
 	}
 
+	/// Electing the active validator set at the end of every epoch
+	pub trait ElectionProvider<AccountId, Balance> {
+		/// Elects at most `to_elect` winners from the online `candidates` in the form of
+		/// `(candidate, bond, total)`. The `edges` are the delegations `(delegator, candidate,
+		/// amount)` of the online candidates. The returned set is expected to be sorted by the
+		/// backing of the winners in descending order.
+		fn elect(
+			to_elect: u32,
+			candidates: Vec<(AccountId, Balance, Balance)>,
+			edges: Vec<(AccountId, AccountId, Balance)>,
+		) -> Vec<(AccountId, Balance, Balance)>;
+
+		/// Whether the election reads the `edges`. The delegations of the candidates are only read
+		/// from storage when it does, otherwise the election receives no edges
+		fn needs_edges() -> bool {
+			true
+		}
+	}
+
 	pub type BalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
 		<T as frame::deps::frame_system::Config>::AccountId,
 	>>::Balance;
 
+	pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+	/// The in-code storage version of the pallet
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -124,6 +156,28 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxActiveValidators: Get<u32>;
 
+		/// The algorithm used to elect the active validator set from the online candidates at the
+		/// end of every epoch. The pallet provides [`crate::TopStakeElection`] and
+		/// [`crate::SequentialPhragmenElection`]
+		type ElectionProvider: ElectionProvider<Self::AccountId, BalanceOf<Self>>;
+
+		/// The upper thresholds of the bags in the sorted candidate index, in ascending order.
+		/// Candidates are put into the bag with the lowest threshold that is greater than or equal
+		/// to their total stake. Candidates above the highest threshold are put into the last bag
+		#[pallet::constant]
+		type BagThresholds: Get<&'static [BalanceOf<Self>]>;
+
+		/// The maximum number of online candidates read from the top bags of the sorted candidate
+		/// index for the election. Must be at least `MaxActiveValidators`
+		#[pallet::constant]
+		type MaxElectableCandidates: Get<u32>;
+
+		/// The maximum number of validators of the current active validator set that can be
+		/// replaced in one election. The strongest challengers get priority and the other
+		/// validators carry over to the next epoch
+		#[pallet::constant]
+		type MaxValidatorChurn: Get<u32>;
+
 		/// The minimum number of candidates in the active validator set
 		/// If there lacks active validators, block production won't happen
 		/// until there is enough validators. This ensure the network stability
@@ -146,9 +200,61 @@ pub mod pallet {
 
 		/// A predefined period during which the set of active validators remains fixed. At the end
 		/// of each epoch, a new set of validators can be elected based on the current delegations.
+		/// This is the default duration, which can be updated on-chain by `set_epoch_duration`.
 		#[pallet::constant]
 		type EpochDuration: Get<BlockNumberFor<Self>>;
 
+		/// The mechanism that moves the pallet to the next epoch. With
+		/// [`EpochTrigger::BlockNumber`], the epoch moves when the epoch duration has elapsed. With
+		/// [`EpochTrigger::Session`], every session of `pallet_session` is an epoch and the pallet
+		/// must be configured as its `SessionManager`. With [`EpochTrigger::Timestamp`], the epoch
+		/// moves when the epoch duration has elapsed in wall-clock time
+		#[pallet::constant]
+		type EpochTrigger: Get<EpochTrigger>;
+
+		/// The source of the wall-clock time, usually `pallet_timestamp`. Only read with
+		/// [`EpochTrigger::Timestamp`]
+		type Time: Time;
+
+		/// The expected time between two blocks in milliseconds. With
+		/// [`EpochTrigger::Timestamp`], the durations in blocks (the epoch duration and the delays
+		/// of the delay actions) are converted into wall-clock durations with this value
+		#[pallet::constant]
+		type ExpectedBlockTime: Get<MomentOf<Self>>;
+
+		/// Number of past epochs whose records are kept in the epoch history, on top of the
+		/// current epoch
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+
+		/// A stable ID for a validator, the same type as the `ValidatorId` of `pallet_session`
+		type ValidatorId: Member + Parameter;
+
+		/// A conversion from account ID to validator ID. With [`EpochTrigger::Session`], the
+		/// candidates must have registered their session keys under this validator ID to register
+		/// and to be elected
+		type ValidatorIdOf: Convert<Self::AccountId, Option<Self::ValidatorId>>;
+
+		/// Validates the registration of the session keys of the candidates, usually
+		/// `pallet_session`
+		type ValidatorRegistration: ValidatorRegistration<Self::ValidatorId>;
+
+		/// Number of blocks before the end of the epoch at which the election of the next epoch
+		/// starts. The electable candidates and their delegations are read page by page, the
+		/// election runs in the block after the last page and the epoch snapshot of the elected set
+		/// is then captured page by page. The election is finalized at the epoch boundary, where
+		/// the pages not processed yet are processed. Zero disables the paged election and
+		/// everything is done at the epoch boundary. The paged election is only used when the
+		/// epochs are moved by block number
+		#[pallet::constant]
+		type ElectionLookahead: Get<BlockNumberFor<Self>>;
+
+		/// The number of candidates processed in one page of the paged election: the candidates
+		/// read from the sorted candidate index, the candidates whose delegations are read, or the
+		/// validators whose delegations are captured in the epoch snapshot
+		#[pallet::constant]
+		type SnapshotPageSize: Get<u32>;
+
 		/// Number of blocks required for the deregister_candidate_method to work
 		#[pallet::constant]
 		type DelayDeregisterCandidateDuration: Get<BlockNumberFor<Self>>;
@@ -157,13 +263,39 @@ pub mod pallet {
 		#[pallet::constant]
 		type DelayUndelegateCandidate: Get<BlockNumberFor<Self>>;
 
-		/// Percentage of commission that the delegator receives for their delegations
+		/// The model used to reward the block authors and their delegators. With
+		/// [`RewardModel::BlockRewards`], the rewards are credited every block. With
+		/// [`RewardModel::EpochPoints`], the validators earn points for the blocks they author and
+		/// `EpochReward` is split between them by points when the epoch ends
+		#[pallet::constant]
+		type RewardModel: Get<RewardModel>;
+
+		/// The reward of an epoch split between its validators by points with
+		/// [`RewardModel::EpochPoints`]
+		#[pallet::constant]
+		type EpochReward: Get<BalanceOf<Self>>;
+
+		/// The source from which the claimed rewards are paid. With [`RewardSource::Pot`], the
+		/// rewards are paid from the reward pot derived from `PalletId`
+		#[pallet::constant]
+		type RewardSource: Get<RewardSource<Self::AccountId>>;
+
+		/// The identifier of the pallet, used to derive the account of the reward pot
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Maximum number of accounts whose rewards are paid out in one `payout_rewards` call
 		#[pallet::constant]
-		type DelegatorCommission: Get<u32>;
+		type MaxPayoutBatch: Get<u32>;
 
-		/// Percentage of commission that the active validator receives for their delegations
+		/// Number of epochs after the last epoch in which an account earned rewards, after which
+		/// its unclaimed rewards are forfeited. Zero disables the expiry of the rewards
 		#[pallet::constant]
-		type AuthorCommission: Get<u32>;
+		type RewardExpiryEpochs: Get<u32>;
+
+		/// The destination of the forfeited rewards
+		#[pallet::constant]
+		type ExpiredRewardDestination: Get<ExpiredRewardDestination<Self::AccountId>>;
 
 		/// Origin that has the authority to control the parameters in the delegated proof of stake
 		/// network
@@ -173,7 +305,8 @@ pub mod pallet {
 		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		/// Report the new validators to the runtime. This is done through a custom trait defined in
-		/// this pallet.
+		/// this pallet. Use `()` if the validator set is only consumed through the `SessionManager`
+		/// implementation.
 		type ReportNewValidatorSet: ReportNewValidatorSet<Self::AccountId>;
 
 		/// Weight information for extrinsics in this pallet.
@@ -183,7 +316,9 @@ pub mod pallet {
 		/// thanks to the `#[runtime]` macro.
 		type RuntimeHoldReason: From<HoldReason>;
 
-		/// Find the author of a block.
+		/// Find the author of a block. The author is rewarded in `on_initialize`. Use `()` if the
+		/// author is noted through the `pallet_authorship::EventHandler` implementation instead.
+		/// If both are configured, the block is rewarded once, to the first author found.
 		type FindAuthor: FindAuthor<Self::AccountId>;
 
 		/// The handler for on slashed action when validator misbehaves
@@ -196,31 +331,207 @@ pub mod pallet {
 	pub type CandidatePool<T: Config> =
 		CountedStorageMap<_, Twox64Concat, T::AccountId, CandidateDetail<T>, OptionQuery>;
 
+	/// Sorted index of the candidates, mapping the upper threshold of the bag with the candidates
+	/// whose total stake falls into the bag
+	#[pallet::storage]
+	pub type CandidateBags<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BalanceOf<T>,
+		BoundedVec<T::AccountId, <T as Config>::MaxCandidates>,
+		ValueQuery,
+	>;
+
+	/// Mapping the candidate with the upper threshold of the bag it is put into
+	#[pallet::storage]
+	pub type CandidateBagOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
 	/// Selected validators for the current epoch
 	#[pallet::storage]
 	#[pallet::getter(fn active_validators)]
-	pub type CurrentActiveValidators<T: Config> = StorageValue<
+	pub type CurrentActiveValidators<T: Config> =
+		StorageValue<_, BoundedCandidateDelegationSet<T>, ValueQuery>;
+
+	/// Snapshots of the current epoch and the last `HistoryDepth` epochs, mapped by the epoch
+	/// index. A snapshot includes the active validator set of the epoch along with their bonds
+	/// and the total delegated to them.
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_snapshot)]
+	pub type EpochSnapshots<T: Config> =
+		StorageMap<_, Twox64Concat, u32, EpochSnapshot<T>, OptionQuery>;
+
+	/// The delegations captured in the epoch snapshots, mapped by `(epoch index, delegator,
+	/// validator)` so that the rewards of a delegator are settled without reading the delegations
+	/// of the others. The delegations of a pruned epoch are kept until its rewards are settled.
+	#[pallet::storage]
+	pub type EpochDelegations<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, u32>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, T::AccountId>,
+		),
+		BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	/// The phase of the paged election in progress, until the validator set of the next epoch is
+	/// elected
+	#[pallet::storage]
+	pub type PagedElectionPhase<T: Config> = StorageValue<_, ElectionPhase, OptionQuery>;
+
+	/// The electable candidates read from the sorted candidate index so far during the paged
+	/// election, in the form of `(candidate, bond, total)`
+	#[pallet::storage]
+	pub type ElectableCandidates<T: Config> = StorageValue<
+		_,
+		BoundedVec<(T::AccountId, BalanceOf<T>, BalanceOf<T>), <T as Config>::MaxCandidates>,
+		ValueQuery,
+	>;
+
+	/// The delegations `(delegator, amount)` of the electable candidates read so far during the
+	/// paged election, mapped by the candidate
+	#[pallet::storage]
+	pub type ElectableCandidateDelegations<T: Config> = StorageMap<
 		_,
-		BoundedVec<(T::AccountId, BalanceOf<T>, BalanceOf<T>), <T as Config>::MaxActiveValidators>,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(T::AccountId, BalanceOf<T>), <T as Config>::MaxCandidateDelegators>,
 		ValueQuery,
 	>;
 
-	/// Snapshot of the last epoch data, which includes the active validator set along with their
-	/// total bonds and delegations. This storage is unbounded but safe, as it only stores `Vec`
-	/// values within a `BoundedVec`. The total number of delegations is limited by the size
-	/// `MaxActiveValidators * MaxCandidateDelegators`.
+	/// The validator set elected for the next epoch while the paged election is in progress
+	#[pallet::storage]
+	pub type PendingValidatorSet<T: Config> =
+		StorageValue<_, BoundedCandidateDelegationSet<T>, OptionQuery>;
+
+	/// The pages of the snapshot of the pending validator set captured so far, mapped by the page
+	/// index. Each page holds the bonds and the total delegations of `SnapshotPageSize`
+	/// validators, the captured delegations are stored in `EpochDelegations` under the next epoch
+	/// index
+	#[pallet::storage]
+	pub type PendingSnapshotPages<T: Config> =
+		StorageMap<_, Twox64Concat, u32, EpochSnapshot<T>, OptionQuery>;
+
+	/// The number of validators of the pending validator set captured in the pending snapshot
 	#[pallet::storage]
-	#[pallet::unbounded]
-	#[pallet::getter(fn last_epoch_snapshot)]
-	pub type LastEpochSnapshot<T: Config> = StorageValue<_, EpochSnapshot<T>, OptionQuery>;
+	pub type SnapshotPageCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-	/// Stores the total claimable rewards for each account, which can be a validator or a
-	/// delegator. The reward points are updated with each block produced
+	/// The last block whose author was rewarded, so that a block is rewarded once when its author
+	/// is found both with `FindAuthor` and through `pallet_authorship`
+	#[pallet::storage]
+	pub type LastRewardedBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// The number of challengers held back by `MaxValidatorChurn` in the last election
+	#[pallet::storage]
+	pub type DeferredChallengers<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Whether an emergency validator set was installed with [`EpochTrigger::Session`] and is
+	/// not planned into a session yet. The next planned session gets the emergency set instead of
+	/// an elected one
+	#[pallet::storage]
+	pub type PendingEmergencySession<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Stores the settled claimable rewards for each account, which can be a validator or a
+	/// delegator. The validator rewards are credited with each block produced while the delegator
+	/// rewards are settled from the reward pools when they are claimed or after the epoch is pruned
 	#[pallet::storage]
-	#[pallet::getter(fn reward_points)]
 	pub type RewardPoints<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+	/// The last epoch in which the accounts with unclaimed reward points earned rewards
+	#[pallet::storage]
+	#[pallet::getter(fn reward_last_earned)]
+	pub type RewardLastEarned<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+	/// The last account checked by the sweep of the expired rewards, the next sweep resumes after
+	/// it. The sweep starts over when it is empty
+	#[pallet::storage]
+	pub type RewardExpiryCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The reward pools of the active validators, mapped by the epoch index and the validator.
+	/// The pools of a pruned epoch are kept until the rewards of its delegators are settled
+	#[pallet::storage]
+	#[pallet::getter(fn reward_pool)]
+	pub type ValidatorRewardPools<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		T::AccountId,
+		ValidatorRewardPool<T>,
+		OptionQuery,
+	>;
+
+	/// The reward per delegated unit of the reward pool already claimed by the delegator, mapped
+	/// by `(epoch index, delegator, validator)`
+	#[pallet::storage]
+	pub type ClaimedRewardPerStake<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, u32>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, T::AccountId>,
+		),
+		FixedU128,
+		ValueQuery,
+	>;
+
+	/// The oldest epoch pruned from the history whose delegator rewards are not settled yet. The
+	/// rewards of the pruned epochs are settled into the reward points page by page in `on_idle`
+	#[pallet::storage]
+	pub type OldestUnsettledEpoch<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The points earned by the validators for the blocks authored in the epochs kept in the
+	/// history, mapped by the epoch index
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_reward_points)]
+	pub type EpochRewardPoints<T: Config> =
+		StorageMap<_, Twox64Concat, u32, EpochPoints<T>, ValueQuery>;
+
+	/// The destination of the rewards of the accounts, `RewardDestination::Free` by default
+	#[pallet::storage]
+	#[pallet::getter(fn reward_destination)]
+	pub type RewardDestinations<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultMaxCommission() -> Perbill {
+		Perbill::one()
+	}
+
+	/// The minimum commission of the candidates, set by the `ConfigControllerOrigin`
+	#[pallet::storage]
+	#[pallet::getter(fn min_commission)]
+	pub type MinCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// The maximum commission of the candidates, set by the `ConfigControllerOrigin`
+	#[pallet::storage]
+	#[pallet::getter(fn max_commission)]
+	pub type MaxCommission<T: Config> = StorageValue<_, Perbill, ValueQuery, DefaultMaxCommission>;
+
+	/// The inflation curve set by the `ConfigControllerOrigin`, used to compute the reward budget
+	/// of the epochs with `RewardModel::EpochPoints` instead of `EpochReward`
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_curve)]
+	pub type ConfiguredInflationCurve<T: Config> = StorageValue<_, InflationCurve, OptionQuery>;
+
+	/// The rewards of the epoch paid out to the validator and its delegators with
+	/// `payout_epoch`, mapped by `(epoch index, validator)`
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_payout)]
+	pub type EpochPayouts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		OptionQuery,
+	>;
+
 	/// The number of candidates that delegators delegated to
 	#[pallet::storage]
 	#[pallet::getter(fn delegate_count)]
@@ -273,6 +584,34 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type EpochIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	/// Records of the current epoch and the last `HistoryDepth` epochs, mapped by the epoch
+	/// index. Older records are pruned when a new epoch starts
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_info)]
+	pub type EpochHistory<T: Config> = StorageMap<_, Twox64Concat, u32, EpochInfo<T>, OptionQuery>;
+
+	/// The epoch duration set by the `ConfigControllerOrigin`, which overrides `EpochDuration`
+	/// from the epoch following the update
+	#[pallet::storage]
+	pub type ConfiguredEpochDuration<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// The mode of forcing the epoch transitions, set by the `ForceOrigin`
+	#[pallet::storage]
+	#[pallet::getter(fn force_epoch)]
+	pub type ForceEpoch<T: Config> = StorageValue<_, Forcing, ValueQuery>;
+
+	/// The block at which the next epoch starts. It is planned with the epoch duration in effect
+	/// when the current epoch starts
+	#[pallet::storage]
+	#[pallet::getter(fn next_epoch_start_block)]
+	pub type NextEpochStartBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The timestamp at which the next epoch starts when the epochs are moved by timestamp. The
+	/// genesis epoch has no timestamp, its end is planned with the first timestamp of the chain
+	#[pallet::storage]
+	#[pallet::getter(fn next_epoch_start_time)]
+	pub type NextEpochStartTime<T: Config> = StorageValue<_, MomentOf<T>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub genesis_candidates: CandidateSet<T>,
@@ -287,16 +626,6 @@ pub mod pallet {
 				"Need at least one active validator for the network to function"
 			);
 
-			assert!(
-				T::AuthorCommission::get() > 0 && T::AuthorCommission::get() <= 100,
-				"Validator commission must be in percentage"
-			);
-
-			assert!(
-				T::DelegatorCommission::get() > 0 && T::DelegatorCommission::get() <= 100,
-				"Delegator commission must be in percentage"
-			);
-
 			assert!(
 				self.balance_rate > 0 && self.balance_rate <= 1000,
 				"Balance rate must be between 0 (0.1%) or 100 (100%)"
@@ -309,29 +638,16 @@ pub mod pallet {
 				assert!(*bond >= T::MinCandidateBond::get(), "Invalid bond for genesis candidate");
 				assert!(visited.insert(candidate.clone()), "Candidate registration duplicates");
 
-				Pallet::<T>::register_as_candidate_inner(&candidate, *bond)
+				Pallet::<T>::register_as_candidate_inner(candidate, *bond)
 					.expect("Register candidate error");
 			}
 
 			BalanceRate::<T>::put(self.balance_rate);
 
 			// Update the active validator set using the data stored in the candidate pool
-			let active_validator_set = Pallet::<T>::select_active_validator_set().to_vec();
-			CurrentActiveValidators::<T>::put(
-				BoundedVec::try_from(active_validator_set.clone())
-					.expect("Exceed limit number of the validators in the active set"),
-			);
-			// Capture the snapshot of the last epoch
-			LastEpochSnapshot::<T>::set(Some(Pallet::<T>::capture_epoch_snapshot(
-				&active_validator_set,
-			)));
-
-			let new_set = CurrentActiveValidators::<T>::get()
-				.iter()
-				.map(|(active_validator, _, _)| active_validator.clone())
-				.collect::<Vec<T::AccountId>>();
-
-			Pallet::<T>::report_new_validators(new_set);
+			let active_validator_set = Pallet::<T>::select_active_validator_set();
+			Pallet::<T>::set_active_validator_set(&active_validator_set);
+			Pallet::<T>::record_epoch_start(Zero::zero(), &active_validator_set);
 		}
 	}
 
@@ -371,6 +687,8 @@ pub mod pallet {
 		},
 		/// Event emitted when the reward is claimed
 		RewardClaimed { claimer: T::AccountId, total_reward: BalanceOf<T> },
+		/// Event emitted when the reward is paid out on behalf of the account
+		RewardPaidOut { who: T::AccountId, paid_by: T::AccountId, total_reward: BalanceOf<T> },
 		/// Event emitted when candidate is delegated
 		NextEpochMoved {
 			last_epoch: u32,
@@ -378,14 +696,79 @@ pub mod pallet {
 			at_block: BlockNumberFor<T>,
 			total_candidates: u64,
 			total_validators: u64,
+			/// Number of validators replaced by the challengers in the new active validator set
+			replaced_validators: u32,
+			/// Number of elected challengers held back by `MaxValidatorChurn`
+			deferred_challengers: u32,
+			/// Validators of the new active validator set that were not in the previous one
+			rotated_in: Vec<T::AccountId>,
+			/// Validators of the previous active validator set that are not in the new one
+			rotated_out: Vec<T::AccountId>,
+		},
+		/// Event emitted when there are not enough online candidates to elect a new active
+		/// validator set, the current active validator set is kept for the next epoch
+		ElectionFailed { epoch: u32, online_candidates: u32, retained_validators: u32 },
+		/// Event emitted when an emergency active validator set is installed
+		EmergencyValidatorSetInstalled { validators: Vec<T::AccountId> },
+		/// Event emitted when a new epoch is recorded in the epoch history
+		EpochStarted {
+			epoch: u32,
+			start_block: BlockNumberFor<T>,
+			planned_end_block: BlockNumberFor<T>,
+			total_validators: u32,
+			total_active_stake: BalanceOf<T>,
+		},
+		/// Event emitted when an epoch ends with the total rewards issued during the epoch
+		EpochEnded { epoch: u32, at_block: BlockNumberFor<T>, total_rewards: BalanceOf<T> },
+		/// Event emitted when the epoch duration is updated, the duration applies from the next
+		/// epoch
+		EpochDurationSet { epoch_duration: BlockNumberFor<T>, effective_from_epoch: u32 },
+		/// Event emitted when the mode of forcing the epoch transitions is updated
+		EpochForcingSet { mode: Forcing },
+		/// Event emitted when the inflation curve is updated, `None` when the fixed `EpochReward`
+		/// is used again
+		InflationCurveSet { curve: Option<InflationCurve> },
+		/// Event emitted when the candidate updates its commission, which applies from the next
+		/// epoch
+		CommissionSet { candidate_id: T::AccountId, commission: Perbill, effective_from_epoch: u32 },
+		/// Event emitted when the bounds of the candidate commissions are updated
+		CommissionBoundsSet { min_commission: Perbill, max_commission: Perbill },
+		/// Event emitted when the account updates the destination of its rewards
+		RewardDestinationSet { who: T::AccountId, destination: RewardDestination<T::AccountId> },
+		/// Event emitted when the claimed rewards are added to the candidate bond or the
+		/// delegation of the account
+		RewardStaked { who: T::AccountId, candidate_id: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the unclaimed rewards of the account expired and are forfeited
+		RewardExpired {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+			destination: ExpiredRewardDestination<T::AccountId>,
+		},
+		/// Event emitted when the reward pot is topped up
+		RewardPotToppedUp { who: T::AccountId, amount: BalanceOf<T> },
+		/// Event emitted when the rewards of an epoch are paid out to the validator and its
+		/// delegators
+		EpochPaidOut {
+			epoch: u32,
+			validator: T::AccountId,
+			validator_reward: BalanceOf<T>,
+			delegator_rewards: BalanceOf<T>,
 		},
+		/// Event emitted when the rewards of the account could not be paid out by
+		/// `payout_rewards`, the account keeps its reward points
+		RewardPayoutFailed { who: T::AccountId, error: DispatchError },
+		/// Event emitted when the expired rewards of the account could not be paid into the
+		/// treasury or burnt from the reward source. The reward points are forfeited and the
+		/// funds stay in the reward source
+		RewardExpiryFailed { who: T::AccountId, amount: BalanceOf<T>, error: DispatchError },
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		#[cfg(feature = "try-runtime")]
-		fn try_state(_: BlockNumberFOr<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
 			Self::do_try_state();
+			Ok(())
 		}
 
 		fn integrity_test() {
@@ -403,52 +786,84 @@ pub mod pallet {
 				T::MinActiveValidators::get() < T::MaxActiveValidators::get(),
 				"Minimum number of validators must be lower than the maximum number of validators"
 			);
+
+			assert!(T::HistoryDepth::get() != 0, "Epoch history depth can't be zero");
+
+			assert!(
+				T::EpochTrigger::get() != EpochTrigger::Timestamp ||
+					!T::ExpectedBlockTime::get().is_zero(),
+				"Expected block time can't be zero when the epochs are moved by timestamp"
+			);
+
+			assert!(
+				T::SnapshotPageSize::get() != 0,
+				"Number of validators captured per snapshot page can't be zero"
+			);
+
+			assert!(
+				T::MaxElectableCandidates::get() >= T::MaxActiveValidators::get(),
+				"Maximum number of electable candidates must cover the active validator set"
+			);
+
+			assert!(
+				T::BagThresholds::get().windows(2).all(|w| w[0] < w[1]),
+				"Bag thresholds must be sorted in strictly ascending order"
+			);
 		}
 
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			if let Some(current_block_author) = Self::find_author() {
-				// Whenever there is a block produced, we retrieve the snapshot of the epoch
-				// The reward for validator and delegator will be calculated based on that
-				if let Some(EpochSnapshot { validators, delegations }) =
-					LastEpochSnapshot::<T>::get()
-				{
-					if let Some(total_bond) = validators.get(&current_block_author) {
-						Self::sync_validator_rewards(
-							&current_block_author,
-							&delegations,
-							total_bond,
-						);
-					}
-				}
+				Self::reward_block_author_once(&current_block_author);
 			}
 
 			// This is a pretty lightweight check that we do EVERY block, but then tells us when an
 			// Epoch has passed...
-			let epoch_indx = n % T::EpochDuration::get();
-			if epoch_indx == BlockNumberFor::<T>::zero() {
-				let active_validator_set = Self::select_active_validator_set();
-
-				// Update a new set of active validators
-				CurrentActiveValidators::<T>::put(
-					BoundedVec::try_from(active_validator_set.to_vec())
-						.expect("Exceed limit number of the validators in the active set"),
-				);
-				// In new epoch, we want to set the CurrentEpochSnapshot to the current dataset
-				LastEpochSnapshot::<T>::set(Some(Pallet::<T>::capture_epoch_snapshot(
-					&active_validator_set,
-				)));
-
-				let new_set = CurrentActiveValidators::<T>::get()
-					.iter()
-					.map(|(active_validator, _, _)| active_validator.clone())
-					.collect::<Vec<T::AccountId>>();
+			let mut weight = Weight::default();
+			let epoch_trigger = T::EpochTrigger::get();
+			if epoch_trigger == EpochTrigger::Session {
+				// The epochs are moved by `pallet_session` through the `SessionManager`
+				return weight;
+			}
+			let force_epoch = ForceEpoch::<T>::get();
+			if force_epoch == Forcing::ForceNone {
+				// The epochs are frozen by the `ForceOrigin`
+				return weight;
+			}
+			let is_epoch_ended = match epoch_trigger {
+				EpochTrigger::Timestamp => Self::is_epoch_time_elapsed(),
+				_ => {
+					let next_epoch_start_block = NextEpochStartBlock::<T>::get();
+					let election_lookahead = T::ElectionLookahead::get();
+					if !election_lookahead.is_zero() &&
+						n.saturating_add(election_lookahead) == next_epoch_start_block
+					{
+						// The election of the next epoch starts ahead of the epoch end and is
+						// processed page by page in the following blocks. The lookahead
+						// that is not shorter than the epoch never matches a block of the epoch,
+						// the election is then done at the epoch boundary
+						weight.saturating_accrue(Self::start_paged_election());
+					} else if n < next_epoch_start_block {
+						weight.saturating_accrue(Self::process_election_page());
+					}
+					n >= next_epoch_start_block
+				},
+			};
 
-				Pallet::<T>::report_new_validators(new_set);
-				Self::move_to_next_epoch(active_validator_set);
+			let is_forced = matches!(force_epoch, Forcing::ForceNew | Forcing::ForceAlways);
+			if is_forced || is_epoch_ended {
+				if force_epoch == Forcing::ForceNew {
+					ForceEpoch::<T>::put(Forcing::NotForcing);
+				}
+				weight.saturating_accrue(Self::finalize_epoch_election());
 			}
-			// We return a default weight because we do not expect you to do weights for your
-			// project... Except for extra credit...
-			return Weight::default();
+			weight
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let weight = Self::settle_pruned_rewards(remaining_weight);
+			weight.saturating_add(Self::sweep_expired_rewards(
+				remaining_weight.saturating_sub(weight),
+			))
 		}
 	}
 
@@ -464,6 +879,8 @@ pub mod pallet {
 		CandidateAlreadyExist,
 		/// Thrown when candidate is not registered yet
 		CandidateDoesNotExist,
+		/// Thrown when the candidate has not registered its session keys
+		MissingSessionKeys,
 		/// Thrown when there is no record of delegation between the delegator and the candidate
 		DelegationDoesNotExist,
 		/// Thrown when the delegated amount is below the minimum threshold
@@ -486,6 +903,34 @@ pub mod pallet {
 		InvalidZeroAmount,
 		/// Thrown when the provided number is not a percentage
 		IsNotPercentage,
+		/// Thrown when the provided validator set is empty
+		EmptyValidatorSet,
+		/// Thrown when the provided validator set contains the same validator more than once
+		DuplicateValidator,
+		/// Thrown when the provided epoch duration is zero
+		InvalidEpochDuration,
+		/// Thrown when the epoch to be paid out has not ended yet
+		EpochNotEnded,
+		/// Thrown when the validator has no reward to be paid out in the epoch
+		NoEpochRewardFound,
+		/// Thrown when the rewards of the validator in the epoch are paid out already
+		EpochAlreadyPaidOut,
+		/// Thrown when the reward source does not have enough funds to pay the rewards
+		InsufficientRewardSource,
+		/// Thrown when the ideal stake or the falloff of the inflation curve is zero, or the
+		/// minimum inflation is above the maximum inflation
+		InvalidInflationCurve,
+		/// Thrown when the commission is not within the minimum and the maximum commission
+		CommissionOutOfBounds,
+		/// Thrown when the candidate has updated its commission already in the current epoch
+		CommissionUpdateTooSoon,
+		/// Thrown when the minimum commission is above the maximum commission
+		InvalidCommissionBounds,
+		/// Thrown when no account is provided to pay out the rewards of
+		EmptyPayoutBatch,
+		/// Thrown when the epoch transitions are forced while the epochs are moved by
+		/// `pallet_session`, which ends the sessions on its own schedule
+		EpochForcingNotSupported,
 	}
 
 	/// A reason for the pallet dpos placing a hold on funds.
@@ -519,6 +964,8 @@ pub mod pallet {
 		/// - `InvalidZeroAmount`: Raised if `bond` is zero.
 		/// - `BelowMinimumCandidateBond`: Raised if `bond` is less than `MinCandidateBond`.
 		/// - `CandidateAlreadyExist`: Raised if the caller is already registered as a candidate.
+		/// - `MissingSessionKeys`: Raised if the caller has not registered its session keys while
+		///   the epochs are driven by `pallet_session`.
 		///
 		/// Emits:
 		/// - `CandidateRegistered`: When a candidate successfully registers, including the
@@ -538,6 +985,7 @@ pub mod pallet {
 
 			// Only hold the funds of a user which has no holds already.
 			ensure!(!Self::is_candidate(&validator), Error::<T>::CandidateAlreadyExist);
+			ensure!(Self::has_session_keys(&validator), Error::<T>::MissingSessionKeys);
 
 			Self::register_as_candidate_inner(&validator, bond)?;
 
@@ -588,6 +1036,7 @@ pub mod pallet {
 			let mut candidate_detail = Self::get_candidate(&validator)?;
 			candidate_detail.bond = candidate_detail.bond.checked_add(&bond).expect("Overflow");
 			CandidatePool::<T>::set(&validator, Some(candidate_detail));
+			Self::update_candidate_bag(&validator);
 
 			T::NativeBalance::hold(&HoldReason::CandidateBondReserved.into(), &validator, bond)?;
 
@@ -659,6 +1108,7 @@ pub mod pallet {
 
 			candidate_detail.update_bond(new_candidate_bond);
 			CandidatePool::<T>::set(&validator, Some(candidate_detail));
+			Self::update_candidate_bag(&validator);
 
 			Self::release_candidate_bonds(&validator, bond)?;
 
@@ -1041,26 +1491,33 @@ pub mod pallet {
 		/// Errors:
 		/// - If no claimable rewards are found for the claimer, the function will return an
 		///   `Error`.
+		/// - `InsufficientRewardSource`: Raised if the reward pot or the treasury account does not
+		///   have enough funds to pay the rewards, the reward points are then kept.
 		///
 		/// Effects:
-		/// - Mints native tokens into the claimer's account equivalent to their accumulated reward
-		///   points.
+		/// - Pays native tokens from the `RewardSource` equivalent to their accumulated reward
+		///   points, to the `RewardDestination` of the claimer.
 		/// - Removes the claimer's accumulated reward points from storage after claiming.
 		/// - Emits a `RewardClaimed` event upon successful claim.
 		///
-		/// Weight: Determined by the pallet's `WeightInfo` implementation for `claim_reward`.
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `claim_reward`,
+		/// charged for the delegations of the claimer in every epoch kept in the history and
+		/// refunded for the delegations that were not read.
 		#[pallet::call_index(13)]
-		#[pallet::weight(<T as Config>::WeightInfo::default())]
-		pub fn claim_reward(origin: OriginFor<T>) -> DispatchResult {
+		#[allow(clippy::useless_conversion)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_reward(
+			Pallet::<T>::max_delegator_reward_claims()
+		))]
+		pub fn claim_reward(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let claimer = ensure_signed(origin)?;
 
+			let total_delegations = Self::settle_delegator_rewards(&claimer);
 			let reward_points = RewardPoints::<T>::try_get(&claimer)
 				.map_err(|_| Error::<T>::NoClaimableRewardFound)?;
 			ensure!(reward_points > Zero::zero(), Error::<T>::NoClaimableRewardFound);
 
-			Self::claim_reward_inner(claimer, reward_points);
-
-			Ok(())
+			Self::claim_reward_inner(claimer, reward_points, None)?;
+			Ok(Some(<T as Config>::WeightInfo::claim_reward(total_delegations)).into())
 		}
 
 		/// Sets the balance rate to control the inflation of the DPoS (Delegated Proof of Stake)
@@ -1097,71 +1554,596 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Forces the reporting of a new validator set in the DPoS (Delegated Proof of Stake)
-		/// network.
+		/// Sets the inflation curve used to compute the reward budget of the epochs. The
+		/// `BalanceRate` set with `force_set_balance_rate` remains used by the legacy
+		/// `RewardModel::BlockRewards`.
 		///
 		/// Parameters:
-		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
-		/// - `new_set`: The new set of validator accounts to be reported.
+		/// - `origin`: The origin of the transaction, which must be authorized by the
+		///   `ConfigControllerOrigin`.
+		/// - `curve`: The new inflation curve, or `None` to use the fixed `EpochReward` again.
 		///
 		/// Errors:
 		/// - If the origin is not authorized to execute this function, it will return an `Error`.
-		/// - Errors from `report_new_validators` if the reporting fails.
+		/// - `InvalidInflationCurve`: Raised if the ideal stake or the falloff is zero, or the
+		///   minimum inflation is above the maximum inflation.
 		///
 		/// Effects:
-		/// - Calls `report_new_validators` with the provided `new_set` to update the validator set.
+		/// - With `RewardModel::EpochPoints`, the reward budget of every ended epoch is computed by
+		///   the curve from the total issuance and the total staked in the epoch snapshot.
+		///
+		/// Emits:
+		/// - `InflationCurveSet`: When the inflation curve is updated.
 		///
 		/// Weight: Determined by the pallet's `WeightInfo` implementation for
-		/// `force_report_new_validators`.
-		#[pallet::call_index(99)]
-		#[pallet::weight(<T as Config>::WeightInfo::force_report_new_validators())]
-		pub fn force_report_new_validators(
+		/// `set_inflation_curve`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_inflation_curve())]
+		pub fn set_inflation_curve(
 			origin: OriginFor<T>,
-			new_set: Vec<T::AccountId>,
+			curve: Option<InflationCurve>,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
-			ensure!(
-				(new_set.len() as u32) < T::MaxCandidates::get(),
-				Error::<T>::TooManyValidators
-			);
-			Self::report_new_validators(new_set);
+			T::ConfigControllerOrigin::ensure_origin(origin)?;
+			ensure!(curve.is_none_or(|curve| curve.is_valid()), Error::<T>::InvalidInflationCurve);
+
+			ConfiguredInflationCurve::<T>::set(curve);
+			Self::deposit_event(Event::InflationCurveSet { curve });
 			Ok(())
 		}
-	}
 
-	#[cfg(any(test, feature = "try-state"))]
-	impl<T: Config> Pallet<T> {
-		pub fn do_try_state() {
-			assert!(
-				BalanceRate::<T>::get() > 0 && BalanceRate::<T>::get() <= 1000,
-				"Balance rate must be between 0 (0.1%) or 1000 (100%)"
+		/// Sets the commission that the candidate takes off its rewards before they are shared
+		/// with its delegators, in both reward models. The commission can be updated once per
+		/// epoch.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be signed by the candidate.
+		/// - `commission`: The new commission of the candidate.
+		///
+		/// Errors:
+		/// - `CandidateDoesNotExist`: Raised if the caller is not a registered candidate.
+		/// - `CommissionOutOfBounds`: Raised if the commission is below `MinCommission` or above
+		///   `MaxCommission`.
+		/// - `CommissionUpdateTooSoon`: Raised if the commission is updated already in the current
+		///   epoch.
+		///
+		/// Effects:
+		/// - Updates the commission of the `CandidateDetail`. The reward pools of the current epoch
+		///   keep the commission captured when the epoch started, the new commission applies from
+		///   the next epoch.
+		///
+		/// Emits:
+		/// - `CommissionSet`: When the commission is updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `set_commission`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResult {
+			let candidate = ensure_signed(origin)?;
+			let mut candidate_detail = Self::get_candidate(&candidate)?;
+			ensure!(
+				commission >= MinCommission::<T>::get() && commission <= MaxCommission::<T>::get(),
+				Error::<T>::CommissionOutOfBounds
+			);
+			let epoch_index = EpochIndex::<T>::get();
+			ensure!(
+				candidate_detail.commission_updated_at != Some(epoch_index),
+				Error::<T>::CommissionUpdateTooSoon
 			);
 
-			let mut visited: BTreeSet<T::AccountId> = BTreeSet::default();
-			for (candidate, candidate_detail) in CandidatePool::<T>::iter() {
-				assert!(
-					candidate_detail.bond >= T::MinCandidateBond::get(),
-					"Invalid bond for genesis candidate"
-				);
-				assert!(visited.insert(candidate.clone()), "Candidate registration duplicates");
-			}
-		}
-	}
+			candidate_detail.update_commission(commission, epoch_index);
+			CandidatePool::<T>::insert(&candidate, candidate_detail);
 
-	impl<T: Config> Pallet<T> {
-		/// Toggles the online status of a candidate in the DPoS network.
-		pub(crate) fn toggle_candidate_status(candidate: &T::AccountId) -> DispatchResult {
-			let mut candidate_detail = Self::get_candidate(&candidate)?;
-			candidate_detail.toggle_status();
-			CandidatePool::<T>::set(&candidate, Some(candidate_detail));
+			Self::deposit_event(Event::CommissionSet {
+				candidate_id: candidate,
+				commission,
+				effective_from_epoch: epoch_index.saturating_add(1),
+			});
 			Ok(())
 		}
 
-		/// Update the epoch index and move to the next epoch
-		pub(crate) fn move_to_next_epoch(active_valivdator_set: CandidateDelegationSet<T>) {
-			let epoch_index = EpochIndex::<T>::get();
-			let next_epoch_index = epoch_index.saturating_add(1);
-			EpochIndex::<T>::set(next_epoch_index);
+		/// Sets the minimum and the maximum commission of the candidates. The commissions of the
+		/// candidates outside of the bounds are clamped into the bounds from the next epoch.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by the
+		///   `ConfigControllerOrigin`.
+		/// - `min_commission`: The new minimum commission.
+		/// - `max_commission`: The new maximum commission.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - `InvalidCommissionBounds`: Raised if `min_commission` is above `max_commission`.
+		///
+		/// Emits:
+		/// - `CommissionBoundsSet`: When the bounds are updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `set_commission_bounds`.
+		#[pallet::call_index(24)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn set_commission_bounds(
+			origin: OriginFor<T>,
+			min_commission: Perbill,
+			max_commission: Perbill,
+		) -> DispatchResult {
+			T::ConfigControllerOrigin::ensure_origin(origin)?;
+			ensure!(min_commission <= max_commission, Error::<T>::InvalidCommissionBounds);
+
+			MinCommission::<T>::put(min_commission);
+			MaxCommission::<T>::put(max_commission);
+			Self::deposit_event(Event::CommissionBoundsSet { min_commission, max_commission });
+			Ok(())
+		}
+
+		/// Sets the destination of the rewards claimed by the caller or paid out on its behalf.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, the account receiving the rewards.
+		/// - `destination`: The new destination of the rewards.
+		///
+		/// Effects:
+		/// - With `RewardDestination::Staked`, the rewards are added to the candidate bond of the
+		///   account, or to its largest delegation when the account is not a candidate. The rewards
+		///   stay in the free balance when they can not be staked.
+		/// - With `RewardDestination::Account`, the rewards are paid into the provided account.
+		///
+		/// Emits:
+		/// - `RewardDestinationSet`: When the destination is updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `set_reward_destination`.
+		#[pallet::call_index(25)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn set_reward_destination(
+			origin: OriginFor<T>,
+			destination: RewardDestination<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if destination == RewardDestination::Free {
+				RewardDestinations::<T>::remove(&who);
+			} else {
+				RewardDestinations::<T>::insert(&who, destination.clone());
+			}
+			Self::deposit_event(Event::RewardDestinationSet { who, destination });
+			Ok(())
+		}
+
+		/// Pays out the accumulated rewards of a batch of accounts (validators or delegators) on
+		/// their behalf. Anyone can call it, for example a keeper bot paying many delegators in one
+		/// transaction.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, any signed account.
+		/// - `accounts`: The accounts whose rewards are paid out, at most `MaxPayoutBatch`.
+		///
+		/// Errors:
+		/// - `EmptyPayoutBatch`: Raised if no account is provided.
+		///
+		/// Effects:
+		/// - Pays native tokens from the `RewardSource` equivalent to the accumulated reward points
+		///   of each account, to the `RewardDestination` of the account.
+		/// - The accounts without rewards are skipped. The accounts whose rewards can not be paid,
+		///   for example because the reward source is insufficient, keep their reward points.
+		///
+		/// Emits:
+		/// - `RewardPaidOut`: For each account whose rewards are paid out.
+		/// - `RewardPayoutFailed`: For each account whose rewards could not be paid out, with the
+		///   error.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `payout_rewards`,
+		/// charged for the delegations of each account in every epoch kept in the history and
+		/// refunded for the delegations that were not read.
+		#[pallet::call_index(26)]
+		#[allow(clippy::useless_conversion)]
+		#[pallet::weight(<T as Config>::WeightInfo::payout_rewards(
+			accounts.len() as u32,
+			(accounts.len() as u32).saturating_mul(Pallet::<T>::max_delegator_reward_claims())
+		))]
+		pub fn payout_rewards(
+			origin: OriginFor<T>,
+			accounts: BoundedVec<T::AccountId, T::MaxPayoutBatch>,
+		) -> DispatchResultWithPostInfo {
+			let paid_by = ensure_signed(origin)?;
+			ensure!(!accounts.is_empty(), Error::<T>::EmptyPayoutBatch);
+
+			let total_accounts = accounts.len() as u32;
+			let mut total_delegations = 0u32;
+			for who in accounts {
+				total_delegations =
+					total_delegations.saturating_add(Self::settle_delegator_rewards(&who));
+				let reward_points = RewardPoints::<T>::get(&who);
+				if reward_points.is_zero() {
+					continue;
+				}
+				if let Err(error) = with_storage_layer(|| {
+					Self::claim_reward_inner(who.clone(), reward_points, Some(paid_by.clone()))
+				}) {
+					Self::deposit_event(Event::RewardPayoutFailed { who, error });
+				}
+			}
+			Ok(Some(<T as Config>::WeightInfo::payout_rewards(total_accounts, total_delegations))
+				.into())
+		}
+
+		/// Installs an emergency active validator set in the DPoS (Delegated Proof of Stake)
+		/// network, for example when the election failed because there are not enough online
+		/// candidates.
+		///
+		/// Requires the caller to have the privilege defined by `ForceOrigin`.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
+		/// - `validators`: The account IDs of the registered candidates to be installed as the
+		///   active validator set.
+		///
+		/// Errors:
+		/// - `EmptyValidatorSet`: Raised if `validators` is empty.
+		/// - `TooManyValidators`: Raised if `validators` exceeds `MaxActiveValidators`.
+		/// - `DuplicateValidator`: Raised if a validator is provided more than once.
+		/// - `CandidateDoesNotExist`: Raised if a validator is not registered as a candidate.
+		/// - `MissingSessionKeys`: Raised if a validator has not registered its session keys while
+		///   the epochs are moved by `pallet_session`.
+		///
+		/// Effects:
+		/// - Replaces the active validator set and the epoch snapshot with the provided validators
+		///   until the next successful election. With `EpochTrigger::Session`, the emergency set
+		///   replaces the validator set planned already and the next planned session gets the
+		///   emergency set instead of an elected one.
+		/// - Settles the unclaimed delegator rewards of the replaced snapshot into the reward
+		///   points of the delegators.
+		/// - Reports the new validator set to the external system.
+		///
+		/// Emits:
+		/// - `EmergencyValidatorSetInstalled`: When the emergency set is installed, including the
+		///   account IDs of the validators.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `settle_pruned_rewards` and `capture_snapshot_page`, for the delegations of a full
+		/// active validator set.
+		#[pallet::call_index(15)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::settle_pruned_rewards(
+				MaxSnapshotDelegations::<T>::get(),
+				T::MaxActiveValidators::get(),
+			)
+			.saturating_add(<T as Config>::WeightInfo::capture_snapshot_page(
+				T::MaxActiveValidators::get(),
+				MaxSnapshotDelegations::<T>::get(),
+			))
+		)]
+		pub fn force_set_emergency_validators(
+			origin: OriginFor<T>,
+			validators: Vec<T::AccountId>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(!validators.is_empty(), Error::<T>::EmptyValidatorSet);
+			ensure!(
+				(validators.len() as u32) <= T::MaxActiveValidators::get(),
+				Error::<T>::TooManyValidators
+			);
+
+			let mut visited: BTreeSet<T::AccountId> = BTreeSet::default();
+			let mut active_validator_set: CandidateDelegationSet<T> = vec![];
+			for validator in validators.iter() {
+				ensure!(visited.insert(validator.clone()), Error::<T>::DuplicateValidator);
+				let candidate_detail = Self::get_candidate(validator)?;
+				ensure!(Self::has_session_keys(validator), Error::<T>::MissingSessionKeys);
+				active_validator_set.push((
+					validator.clone(),
+					candidate_detail.bond,
+					candidate_detail.total(),
+				));
+			}
+
+			Self::set_active_validator_set(&active_validator_set);
+			if T::EpochTrigger::get() == EpochTrigger::Session {
+				// The validators of the sessions are only updated when a session is planned, the
+				// emergency set replaces the planned set and is planned into the next session
+				Self::clear_pending_election();
+				Self::store_session_validator_set(active_validator_set);
+				PendingEmergencySession::<T>::put(true);
+			}
+			Self::deposit_event(Event::EmergencyValidatorSetInstalled { validators });
+			Ok(())
+		}
+
+		/// Sets the duration of the epochs in the DPoS (Delegated Proof of Stake) network. The
+		/// current epoch keeps its planned end, the new duration applies from the next epoch.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by the
+		///   `ConfigControllerOrigin`.
+		/// - `epoch_duration`: The new number of blocks of an epoch.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - `InvalidEpochDuration`: Raised if `epoch_duration` is zero.
+		///
+		/// Effects:
+		/// - Updates the `ConfiguredEpochDuration` storage value, which overrides `EpochDuration`.
+		///   The duration is not used when the epochs are moved by `pallet_session`, and is
+		///   converted into wall-clock time with `ExpectedBlockTime` when the epochs are moved by
+		///   timestamp.
+		///
+		/// Emits:
+		/// - `EpochDurationSet`: When the duration is updated, including the new duration and the
+		///   index of the first epoch it applies to.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `set_epoch_duration`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn set_epoch_duration(
+			origin: OriginFor<T>,
+			epoch_duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::ConfigControllerOrigin::ensure_origin(origin)?;
+			ensure!(!epoch_duration.is_zero(), Error::<T>::InvalidEpochDuration);
+
+			ConfiguredEpochDuration::<T>::put(epoch_duration);
+			Self::deposit_event(Event::EpochDurationSet {
+				epoch_duration,
+				effective_from_epoch: EpochIndex::<T>::get().saturating_add(1),
+			});
+			Ok(())
+		}
+
+		/// Forces a new epoch to start at the next block, for example to re-elect the active
+		/// validator set after a mass slash. The forcing mode is reset to `NotForcing` once the
+		/// epoch moved.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - `EpochForcingNotSupported`: Raised if the epochs are moved by `pallet_session`.
+		///
+		/// Effects:
+		/// - Sets `ForceEpoch` to `ForceNew`. The forcing is honoured when the epochs are moved by
+		///   block number or by timestamp.
+		///
+		/// Emits:
+		/// - `EpochForcingSet`: When the forcing mode is updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `force_new_epoch`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn force_new_epoch(origin: OriginFor<T>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::set_force_epoch(Forcing::ForceNew)
+		}
+
+		/// Stops the epoch transitions so that the active validator set is frozen, for example
+		/// during an incident. The epochs move again after `force_new_epoch` or
+		/// `force_new_epoch_always`.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - `EpochForcingNotSupported`: Raised if the epochs are moved by `pallet_session`.
+		///
+		/// Effects:
+		/// - Sets `ForceEpoch` to `ForceNone`. The forcing is honoured when the epochs are moved by
+		///   block number or by timestamp.
+		/// - Discards the paged election in progress.
+		///
+		/// Emits:
+		/// - `EpochForcingSet`: When the forcing mode is updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `force_no_epochs`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn force_no_epochs(origin: OriginFor<T>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::set_force_epoch(Forcing::ForceNone)?;
+			Self::clear_pending_election();
+			Ok(())
+		}
+
+		/// Forces a new epoch to start at every block until the forcing mode is updated again.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - `EpochForcingNotSupported`: Raised if the epochs are moved by `pallet_session`.
+		///
+		/// Effects:
+		/// - Sets `ForceEpoch` to `ForceAlways`. The forcing is honoured when the epochs are moved
+		///   by block number or by timestamp.
+		///
+		/// Emits:
+		/// - `EpochForcingSet`: When the forcing mode is updated.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `force_new_epoch_always`.
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn force_new_epoch_always(origin: OriginFor<T>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::set_force_epoch(Forcing::ForceAlways)
+		}
+
+		/// Pays out the rewards of a validator in an ended epoch kept in the history. Anyone can
+		/// pay out the rewards on behalf of the validator and its delegators.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, any signed account.
+		/// - `validator`: The validator of the epoch to be paid out.
+		/// - `epoch`: The index of the ended epoch.
+		///
+		/// Errors:
+		/// - `EpochNotEnded`: Raised if the epoch is the current epoch or a future epoch.
+		/// - `EpochAlreadyPaidOut`: Raised if the validator is paid out already for the epoch.
+		/// - `NoEpochRewardFound`: Raised if the validator has not earned any points in the epoch
+		///   or the epoch is not kept in the history anymore.
+		///
+		/// Effects:
+		/// - The share of the epoch reward earned by the points of the validator is split between
+		///   the validator and its delegators pro-rata to the bond and the delegations captured in
+		///   the epoch snapshot. The validator reward is added to its `RewardPoints` and the
+		///   delegator rewards are accrued in the reward pool of the validator, to be claimed with
+		///   `claim_reward`.
+		///
+		/// Emits:
+		/// - `EpochPaidOut`: When the rewards are paid out.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `payout_epoch`.
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn payout_epoch(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			epoch: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_payout_epoch(validator, epoch)
+		}
+
+		/// Tops up the reward pot of the pallet, from which the rewards are paid with
+		/// `RewardSource::Pot`.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, the account funding the reward pot.
+		/// - `amount`: The amount transferred into the reward pot.
+		///
+		/// Errors:
+		/// - `InvalidZeroAmount`: Raised if `amount` is zero.
+		/// - Errors from the transfer if the account does not have enough free balance.
+		///
+		/// Effects:
+		/// - Transfers `amount` from the caller into the reward pot account, keeping the caller
+		///   account alive.
+		///
+		/// Emits:
+		/// - `RewardPotToppedUp`: When the reward pot is topped up.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for `top_up_reward_pot`.
+		#[pallet::call_index(21)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn top_up_reward_pot(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(amount > Zero::zero(), Error::<T>::InvalidZeroAmount);
+
+			T::NativeBalance::transfer(
+				&who,
+				&Self::reward_pot_account(),
+				amount,
+				Preservation::Preserve,
+			)?;
+
+			Self::deposit_event(Event::RewardPotToppedUp { who, amount });
+			Ok(())
+		}
+
+		/// Forces the reporting of a new validator set in the DPoS (Delegated Proof of Stake)
+		/// network.
+		///
+		/// Parameters:
+		/// - `origin`: The origin of the transaction, which must be authorized by `ForceOrigin`.
+		/// - `new_set`: The new set of validator accounts to be reported.
+		///
+		/// Errors:
+		/// - If the origin is not authorized to execute this function, it will return an `Error`.
+		/// - Errors from `report_new_validators` if the reporting fails.
+		///
+		/// Effects:
+		/// - Calls `report_new_validators` with the provided `new_set` to update the validator set.
+		///
+		/// Weight: Determined by the pallet's `WeightInfo` implementation for
+		/// `force_report_new_validators`.
+		#[pallet::call_index(99)]
+		#[pallet::weight(<T as Config>::WeightInfo::force_report_new_validators())]
+		pub fn force_report_new_validators(
+			origin: OriginFor<T>,
+			new_set: Vec<T::AccountId>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(
+				(new_set.len() as u32) < T::MaxCandidates::get(),
+				Error::<T>::TooManyValidators
+			);
+			Self::report_new_validators(new_set);
+			Ok(())
+		}
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	impl<T: Config> Pallet<T> {
+		pub fn do_try_state() {
+			assert!(
+				BalanceRate::<T>::get() > 0 && BalanceRate::<T>::get() <= 1000,
+				"Balance rate must be between 0 (0.1%) or 1000 (100%)"
+			);
+
+			let mut visited: BTreeSet<T::AccountId> = BTreeSet::default();
+			for (candidate, candidate_detail) in CandidatePool::<T>::iter() {
+				assert!(
+					candidate_detail.bond >= T::MinCandidateBond::get(),
+					"Invalid bond for genesis candidate"
+				);
+				assert!(visited.insert(candidate.clone()), "Candidate registration duplicates");
+				assert_eq!(
+					CandidateBagOf::<T>::get(&candidate),
+					Some(Self::bag_threshold_of(candidate_detail.total())),
+					"Candidate is not in the bag matching its total stake"
+				);
+			}
+
+			let mut total_bagged = 0;
+			for (threshold, bag) in CandidateBags::<T>::iter() {
+				for candidate in bag.iter() {
+					assert_eq!(
+						CandidateBagOf::<T>::get(candidate),
+						Some(threshold),
+						"Bag contains a candidate of another bag"
+					);
+				}
+				total_bagged += bag.len();
+			}
+			assert_eq!(
+				total_bagged as u32,
+				CandidatePool::<T>::count(),
+				"Sorted candidate index is out of sync with the candidate pool"
+			);
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Toggles the online status of a candidate in the DPoS network.
+		pub(crate) fn toggle_candidate_status(candidate: &T::AccountId) -> DispatchResult {
+			let mut candidate_detail = Self::get_candidate(candidate)?;
+			candidate_detail.toggle_status();
+			CandidatePool::<T>::set(candidate, Some(candidate_detail));
+			Ok(())
+		}
+
+		/// Update the epoch index and move to the next epoch
+		pub(crate) fn move_to_next_epoch(
+			active_valivdator_set: CandidateDelegationSet<T>,
+			(rotated_in, rotated_out): (Vec<T::AccountId>, Vec<T::AccountId>),
+			deferred_challengers: u32,
+		) {
+			let epoch_index = EpochIndex::<T>::get();
+			let next_epoch_index = epoch_index.saturating_add(1);
+			EpochIndex::<T>::set(next_epoch_index);
+
+			if T::RewardModel::get() == RewardModel::EpochPoints {
+				Self::allocate_epoch_reward(epoch_index);
+			}
+			if let Some(epoch_info) = EpochHistory::<T>::get(epoch_index) {
+				Self::deposit_event(Event::EpochEnded {
+					epoch: epoch_index,
+					at_block: frame::deps::frame_system::Pallet::<T>::block_number(),
+					total_rewards: epoch_info.total_rewards,
+				});
+			}
+			Self::record_epoch_start(next_epoch_index, &active_valivdator_set);
 
 			Self::deposit_event(Event::NextEpochMoved {
 				last_epoch: epoch_index,
@@ -1169,31 +2151,765 @@ pub mod pallet {
 				at_block: frame::deps::frame_system::Pallet::<T>::block_number(),
 				total_candidates: CandidatePool::<T>::count() as u64,
 				total_validators: active_valivdator_set.len() as u64,
+				replaced_validators: rotated_in.len().min(rotated_out.len()) as u32,
+				deferred_challengers,
+				rotated_in,
+				rotated_out,
+			});
+		}
+
+		/// Records the start of the epoch with its active validator set in the epoch history, plans
+		/// the start of the next epoch and prunes the record and the snapshot that are older than
+		/// `HistoryDepth`
+		pub(crate) fn record_epoch_start(
+			epoch_index: u32,
+			active_validator_set: &CandidateDelegationSet<T>,
+		) {
+			let start_block = frame::deps::frame_system::Pallet::<T>::block_number();
+			let planned_end_block = start_block.saturating_add(Self::epoch_duration());
+			NextEpochStartBlock::<T>::put(planned_end_block);
+			if T::EpochTrigger::get() == EpochTrigger::Timestamp {
+				Self::plan_next_epoch_start_time();
+			}
+			let validators = BoundedVec::truncate_from(
+				active_validator_set
+					.iter()
+					.map(|(active_validator, _, _)| active_validator.clone())
+					.collect(),
+			);
+			let total_active_stake = active_validator_set
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |total, (_, _, stake)| total.saturating_add(*stake));
+
+			EpochHistory::<T>::insert(
+				epoch_index,
+				EpochInfo::new(start_block, planned_end_block, validators, total_active_stake),
+			);
+			if let Some(pruned_epoch_index) =
+				epoch_index.checked_sub(T::HistoryDepth::get().saturating_add(1))
+			{
+				// The delegations and the reward pools of the pruned epoch are kept until the
+				// rewards of its delegators are settled in `on_idle`
+				EpochHistory::<T>::remove(pruned_epoch_index);
+				EpochSnapshots::<T>::remove(pruned_epoch_index);
+				EpochRewardPoints::<T>::remove(pruned_epoch_index);
+				let _ = EpochPayouts::<T>::clear_prefix(pruned_epoch_index, u32::MAX, None);
+			}
+
+			Self::deposit_event(Event::EpochStarted {
+				epoch: epoch_index,
+				start_block,
+				planned_end_block,
+				total_validators: active_validator_set.len() as u32,
+				total_active_stake,
+			});
+		}
+
+		/// Updates the mode of forcing the epoch transitions
+		fn set_force_epoch(mode: Forcing) -> DispatchResult {
+			ensure!(
+				T::EpochTrigger::get() != EpochTrigger::Session,
+				Error::<T>::EpochForcingNotSupported
+			);
+			ForceEpoch::<T>::put(mode);
+			Self::deposit_event(Event::EpochForcingSet { mode });
+			Ok(())
+		}
+
+		/// The epoch duration in effect, either set by `set_epoch_duration` or `EpochDuration`
+		pub fn epoch_duration() -> BlockNumberFor<T> {
+			ConfiguredEpochDuration::<T>::get().unwrap_or_else(T::EpochDuration::get)
+		}
+
+		/// Converts a duration in blocks into a wall-clock duration with `ExpectedBlockTime`
+		pub fn blocks_to_time(blocks: BlockNumberFor<T>) -> MomentOf<T> {
+			let blocks: u32 = blocks.unique_saturated_into();
+			T::ExpectedBlockTime::get().saturating_mul(blocks.into())
+		}
+
+		/// Plans the timestamp at which the next epoch starts from the current timestamp. Nothing
+		/// is planned before the first timestamp of the chain is set
+		fn plan_next_epoch_start_time() {
+			let now = T::Time::now();
+			if now.is_zero() {
+				NextEpochStartTime::<T>::kill();
+			} else {
+				NextEpochStartTime::<T>::put(
+					now.saturating_add(Self::blocks_to_time(Self::epoch_duration())),
+				);
+			}
+		}
+
+		/// Whether the wall-clock duration of the current epoch has elapsed. The timestamp of the
+		/// block is not set yet in `on_initialize`, so the epoch moves at the first block after the
+		/// block whose timestamp reached the planned start of the next epoch
+		fn is_epoch_time_elapsed() -> bool {
+			match NextEpochStartTime::<T>::get() {
+				Some(next_epoch_start_time) => T::Time::now() >= next_epoch_start_time,
+				None => {
+					// The end of the genesis epoch is planned with the first timestamp
+					Self::plan_next_epoch_start_time();
+					false
+				},
+			}
+		}
+
+		/// The record of the current epoch
+		pub fn current_epoch_info() -> Option<EpochInfo<T>> {
+			EpochHistory::<T>::get(EpochIndex::<T>::get())
+		}
+
+		/// The snapshot of the current epoch
+		pub fn last_epoch_snapshot() -> Option<EpochSnapshot<T>> {
+			EpochSnapshots::<T>::get(EpochIndex::<T>::get())
+		}
+
+		/// The validators rotated in and out of the active validator set when the provided set
+		/// replaces the current one
+		fn validator_set_rotation(
+			new_set: &CandidateDelegationSet<T>,
+		) -> (Vec<T::AccountId>, Vec<T::AccountId>) {
+			let current_set = CurrentActiveValidators::<T>::get()
+				.into_iter()
+				.map(|(validator, _, _)| validator)
+				.collect::<BTreeSet<T::AccountId>>();
+			let new_set = new_set
+				.iter()
+				.map(|(validator, _, _)| validator.clone())
+				.collect::<BTreeSet<T::AccountId>>();
+
+			let rotated_in = new_set.difference(&current_set).cloned().collect::<Vec<_>>();
+			let rotated_out = current_set.difference(&new_set).cloned().collect::<Vec<_>>();
+			(rotated_in, rotated_out)
+		}
+
+		/// Elects the active validator set from the online candidates using the configured
+		/// `ElectionProvider`, ensuring the set does not exceed the configured maximum and does
+		/// not replace more than `MaxValidatorChurn` validators of the current set.
+		pub(crate) fn select_active_validator_set() -> CandidateDelegationSet<T> {
+			let (active_validator_set, deferred_challengers) = Self::elect_active_validator_set();
+			DeferredChallengers::<T>::put(deferred_challengers);
+			active_validator_set
+		}
+
+		/// Runs the election of `select_active_validator_set` without writing to storage. Returns
+		/// the elected set and the number of challengers held back by `MaxValidatorChurn`
+		fn elect_active_validator_set() -> (CandidateDelegationSet<T>, u32) {
+			Self::elect_from_candidates(
+				Self::get_electable_candidate_set(),
+				Self::get_delegation_edges,
+			)
+		}
+
+		/// Elects the active validator set from the provided electable candidates. The delegation
+		/// edges are only read with `get_edges` when the `ElectionProvider` needs them
+		fn elect_from_candidates(
+			online_candidates: CandidateDelegationSet<T>,
+			get_edges: impl FnOnce(&CandidateDelegationSet<T>) -> DelegationEdges<T>,
+		) -> (CandidateDelegationSet<T>, u32) {
+			// If the number of candidates is below the threshold for active set, network won't
+			// function
+			if (online_candidates.len() as u32) < T::MinActiveValidators::get() {
+				return (vec![], 0);
+			}
+			let total_in_active_set = T::MaxActiveValidators::get();
+			let delegation_edges = if T::ElectionProvider::needs_edges() {
+				get_edges(&online_candidates)
+			} else {
+				Vec::new()
+			};
+			let mut elected = T::ElectionProvider::elect(
+				total_in_active_set,
+				online_candidates,
+				delegation_edges,
+			);
+			elected.truncate(total_in_active_set as usize);
+			Self::limit_validator_churn(elected)
+		}
+
+		/// Previews the active validator set of the next epoch without writing to storage.
+		///
+		/// Returns the predicted winners in the form of `(candidate, bond, total)` and the online
+		/// candidates that are not predicted to win in the form of `(candidate, total, gap)`,
+		/// sorted by the stake they miss to reach the cut-off, the lowest total of the winners.
+		/// If the paged election is in progress, the pending validator set is returned as the
+		/// winners. If the election would fail, the current active validator set is kept.
+		pub fn preview_next_validator_set() -> (CandidateDelegationSet<T>, CandidateDelegationSet<T>)
+		{
+			let mut winners = match PendingValidatorSet::<T>::get() {
+				Some(pending_validator_set) => pending_validator_set
+					.into_iter()
+					.filter(|(validator, _, _)| Self::is_online_candidate(validator))
+					.collect(),
+				None => Self::elect_active_validator_set().0,
+			};
+			if Self::is_election_failed(&winners) {
+				winners = CurrentActiveValidators::<T>::get().to_vec();
+			}
+
+			let cut_off = winners.iter().map(|(_, _, total)| *total).min().unwrap_or_default();
+			let winner_ids = winners
+				.iter()
+				.map(|(winner, _, _)| winner.clone())
+				.collect::<BTreeSet<T::AccountId>>();
+			let mut losers: CandidateDelegationSet<T> = Self::get_online_candidate_set()
+				.into_iter()
+				.filter(|(candidate, _, _)| !winner_ids.contains(candidate))
+				.map(|(candidate, _, total)| (candidate, total, cut_off.saturating_sub(total)))
+				.collect();
+			losers.sort_by_key(|&(_, _, gap)| gap);
+			(winners, losers)
+		}
+
+		/// Caps the number of challengers, elected candidates that are not in the current active
+		/// validator set, to `MaxValidatorChurn`. The strongest challengers get priority and the
+		/// slots of the other challengers are given back to the strongest outgoing validators that
+		/// are still online. Returns the capped set and the number of challengers held back
+		pub(crate) fn limit_validator_churn(
+			elected: CandidateDelegationSet<T>,
+		) -> (CandidateDelegationSet<T>, u32) {
+			let current_set = CurrentActiveValidators::<T>::get();
+			let current_ids = current_set
+				.iter()
+				.map(|(validator, _, _)| validator.clone())
+				.collect::<BTreeSet<T::AccountId>>();
+			let total_challengers = elected
+				.iter()
+				.filter(|(candidate, _, _)| !current_ids.contains(candidate))
+				.count();
+			let max_churn = T::MaxValidatorChurn::get() as usize;
+			if current_ids.is_empty() || total_challengers <= max_churn {
+				return (elected, 0);
+			}
+
+			// Outgoing validators that are still online carry over, the strongest first
+			let elected_ids = elected
+				.iter()
+				.map(|(candidate, _, _)| candidate.clone())
+				.collect::<BTreeSet<T::AccountId>>();
+			let mut carried_over: CandidateDelegationSet<T> = current_set
+				.into_iter()
+				.filter(|(validator, _, _)| !elected_ids.contains(validator))
+				.filter_map(|(validator, _, _)| {
+					let candidate_detail = CandidatePool::<T>::get(&validator)?;
+					(candidate_detail.status == ValidatorStatus::Online).then(|| {
+						let total = candidate_detail.total();
+						(validator, candidate_detail.bond, total)
+					})
+				})
+				.collect();
+			carried_over.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+			carried_over.truncate(total_challengers - max_churn);
+
+			// The elected set is sorted, so the strongest challengers come first
+			let mut admitted_challengers = total_challengers - carried_over.len();
+			let deferred_challengers = total_challengers - admitted_challengers;
+			let mut active_validator_set: CandidateDelegationSet<T> = elected
+				.into_iter()
+				.filter(|(candidate, _, _)| {
+					if current_ids.contains(candidate) {
+						return true;
+					}
+					if admitted_challengers == 0 {
+						return false;
+					}
+					admitted_challengers -= 1;
+					true
+				})
+				.collect();
+			active_validator_set.extend(carried_over);
+			active_validator_set.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+			(active_validator_set, deferred_challengers as u32)
+		}
+
+		/// Whether the elected set can't be used as the active validator set of the next epoch
+		pub(crate) fn is_election_failed(elected: &CandidateDelegationSet<T>) -> bool {
+			elected.is_empty() || (elected.len() as u32) < T::MinActiveValidators::get()
+		}
+
+		/// Starts the paged election of the validator set of the next epoch and processes its
+		/// first page. Every following block processes one page until the epoch boundary: the
+		/// electable candidates are read from the sorted candidate index, then their delegations
+		/// if the `ElectionProvider` needs them, then the election runs and the elected set is
+		/// stored as pending, and finally the snapshot of the pending set is captured.
+		///
+		/// The candidates and their delegations are read as they are when their page is
+		/// processed. If the election fails, nothing is stored and the election is retried at the
+		/// epoch boundary.
+		pub(crate) fn start_paged_election() -> Weight {
+			Self::clear_pending_election();
+			let mut weight =
+				Self::clear_epoch_delegations(EpochIndex::<T>::get().saturating_add(1));
+			PagedElectionPhase::<T>::put(ElectionPhase::Candidates { bag: 0, position: 0 });
+			weight.saturating_accrue(Self::process_election_page());
+			weight
+		}
+
+		/// Processes the next page of the paged election in progress. Once the validator set is
+		/// elected, the pages of its snapshot are captured
+		pub(crate) fn process_election_page() -> Weight {
+			match PagedElectionPhase::<T>::get() {
+				Some(ElectionPhase::Candidates { bag, position }) =>
+					Self::read_electable_candidates_page(bag, position),
+				Some(ElectionPhase::Delegations { cursor }) =>
+					Self::read_candidate_delegations_page(cursor),
+				Some(ElectionPhase::Elect) => Self::elect_pending_validator_set(),
+				None => Self::capture_next_snapshot_page(),
+			}
+		}
+
+		/// Reads the next `SnapshotPageSize` candidates of the sorted candidate index from the
+		/// bags with the highest thresholds. As in `get_electable_candidate_set`, a bag is always
+		/// read entirely and no more bag is read once `MaxElectableCandidates` candidates are read
+		fn read_electable_candidates_page(mut bag: u32, mut position: u32) -> Weight {
+			let thresholds = Self::bag_thresholds();
+			let mut electable_candidates = ElectableCandidates::<T>::get();
+			let mut remaining = T::SnapshotPageSize::get();
+			let (mut total_bags, mut total_candidates) = (0u32, 0u32);
+			let next_phase = loop {
+				if position == 0 &&
+					(bag as usize >= thresholds.len() ||
+						electable_candidates.len() as u32 >= T::MaxElectableCandidates::get())
+				{
+					break if T::ElectionProvider::needs_edges() {
+						ElectionPhase::Delegations { cursor: 0 }
+					} else {
+						ElectionPhase::Elect
+					};
+				}
+				if remaining == 0 {
+					break ElectionPhase::Candidates { bag, position };
+				}
+
+				let threshold = thresholds[thresholds.len() - 1 - bag as usize];
+				let members = CandidateBags::<T>::get(threshold);
+				total_bags.saturating_inc();
+				let page = members.iter().skip(position as usize).take(remaining as usize);
+				for candidate in page {
+					total_candidates.saturating_inc();
+					let Some(candidate_detail) = CandidatePool::<T>::get(candidate) else {
+						continue;
+					};
+					// The candidates moved to another bag since the election started may be read
+					// twice
+					let is_read = electable_candidates.iter().any(|(read, _, _)| read == candidate);
+					if candidate_detail.status == ValidatorStatus::Online &&
+						Self::has_session_keys(candidate) &&
+						!is_read
+					{
+						electable_candidates
+							.try_push((
+								candidate.clone(),
+								candidate_detail.bond,
+								candidate_detail.total(),
+							))
+							.expect("Candidates are bounded by the maximum number of candidates");
+					}
+				}
+
+				let read = (members.len() as u32).saturating_sub(position).min(remaining);
+				remaining.saturating_reduce(read);
+				position.saturating_accrue(read);
+				if position as usize >= members.len() {
+					bag.saturating_inc();
+					position = 0;
+				}
+			};
+
+			ElectableCandidates::<T>::put(electable_candidates);
+			PagedElectionPhase::<T>::put(next_phase);
+			<T as Config>::WeightInfo::read_electable_candidates(total_candidates)
+				.saturating_add(T::DbWeight::get().reads(total_bags.into()))
+		}
+
+		/// Reads the delegations of the next `SnapshotPageSize` electable candidates
+		fn read_candidate_delegations_page(cursor: u32) -> Weight {
+			let electable_candidates = ElectableCandidates::<T>::get();
+			let page_end = cursor
+				.saturating_add(T::SnapshotPageSize::get())
+				.min(electable_candidates.len() as u32);
+			let mut total_delegations = 0u32;
+			for (candidate, _, _) in
+				electable_candidates.iter().take(page_end as usize).skip(cursor as usize)
+			{
+				let delegations = Self::get_candidate_delegations(candidate);
+				total_delegations.saturating_accrue(delegations.len() as u32);
+				ElectableCandidateDelegations::<T>::insert(
+					candidate,
+					BoundedVec::truncate_from(delegations),
+				);
+			}
+
+			let next_phase = if page_end as usize >= electable_candidates.len() {
+				ElectionPhase::Elect
+			} else {
+				ElectionPhase::Delegations { cursor: page_end }
+			};
+			PagedElectionPhase::<T>::put(next_phase);
+			<T as Config>::WeightInfo::read_candidate_delegations(
+				page_end.saturating_sub(cursor),
+				total_delegations,
+			)
+		}
+
+		/// Elects the validator set of the next epoch from the electable candidates and the
+		/// delegations read by the previous pages and stores it as pending
+		fn elect_pending_validator_set() -> Weight {
+			let mut electable_candidates = ElectableCandidates::<T>::take().into_inner();
+			let total_candidates = electable_candidates.len() as u32;
+			electable_candidates.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+			electable_candidates.truncate(T::MaxElectableCandidates::get() as usize);
+
+			let mut total_edges = 0u32;
+			let (pending_validator_set, deferred_challengers) =
+				Self::elect_from_candidates(electable_candidates, |candidates| {
+					let edges = candidates
+						.iter()
+						.flat_map(|(candidate, _, _)| {
+							ElectableCandidateDelegations::<T>::get(candidate).into_iter().map(
+								move |(delegator, amount)| (delegator, candidate.clone(), amount),
+							)
+						})
+						.collect::<DelegationEdges<T>>();
+					total_edges = edges.len() as u32;
+					edges
+				});
+			let _ = ElectableCandidateDelegations::<T>::clear(T::MaxCandidates::get(), None);
+			PagedElectionPhase::<T>::kill();
+			DeferredChallengers::<T>::put(deferred_challengers);
+
+			if !Self::is_election_failed(&pending_validator_set) {
+				PendingValidatorSet::<T>::put(
+					BoundedVec::try_from(pending_validator_set)
+						.expect("Exceed limit number of the validators in the active set"),
+				);
+			}
+			<T as Config>::WeightInfo::elect_validators(total_candidates, total_edges)
+		}
+
+		/// Captures the delegations of the next `SnapshotPageSize` validators of the pending
+		/// validator set into a new page of the pending snapshot
+		pub(crate) fn capture_next_snapshot_page() -> Weight {
+			let Some(pending_validator_set) = PendingValidatorSet::<T>::get() else {
+				return T::DbWeight::get().reads(1);
+			};
+			let cursor = SnapshotPageCursor::<T>::get() as usize;
+			if cursor >= pending_validator_set.len() {
+				return T::DbWeight::get().reads(2);
+			}
+
+			let page_end = cursor
+				.saturating_add(T::SnapshotPageSize::get() as usize)
+				.min(pending_validator_set.len());
+			let page = &pending_validator_set[cursor..page_end];
+			let mut page_snapshot = EpochSnapshot::<T>::default();
+			let total_delegations = Self::capture_snapshot_page(
+				EpochIndex::<T>::get().saturating_add(1),
+				&mut page_snapshot,
+				page,
+			);
+
+			let page_index = (cursor as u32) / T::SnapshotPageSize::get();
+			PendingSnapshotPages::<T>::insert(page_index, page_snapshot);
+			SnapshotPageCursor::<T>::put(page_end as u32);
+			<T as Config>::WeightInfo::capture_snapshot_page(page.len() as u32, total_delegations)
+		}
+
+		/// Finalizes the election at the epoch boundary. The pending validator set is installed
+		/// with its snapshot if the paged election is in progress, otherwise the election and the
+		/// snapshot capture are done in this block.
+		pub(crate) fn finalize_epoch_election() -> Weight {
+			let mut weight = Weight::default();
+			let next_epoch_index = EpochIndex::<T>::get().saturating_add(1);
+			// Process the pages of the paged election that are not processed yet
+			while PagedElectionPhase::<T>::exists() {
+				weight.saturating_accrue(Self::process_election_page());
+			}
+			let active_validator_set = match PendingValidatorSet::<T>::get() {
+				Some(pending_validator_set) => {
+					// Capture the pages that are not captured yet
+					while (SnapshotPageCursor::<T>::get() as usize) < pending_validator_set.len() {
+						weight.saturating_accrue(Self::capture_next_snapshot_page());
+					}
+					// Candidates that left or turned offline since the election started are not
+					// installed
+					pending_validator_set
+						.into_iter()
+						.filter(|(validator, _, _)| Self::is_online_candidate(validator))
+						.collect::<CandidateDelegationSet<T>>()
+				},
+				None => {
+					let max_edges = if T::ElectionProvider::needs_edges() {
+						T::MaxElectableCandidates::get()
+							.saturating_mul(T::MaxCandidateDelegators::get())
+					} else {
+						0
+					};
+					weight.saturating_accrue(<T as Config>::WeightInfo::elect_validators(
+						T::MaxElectableCandidates::get(),
+						max_edges,
+					));
+					Self::select_active_validator_set()
+				},
+			};
+			let pending_snapshot = Self::pending_epoch_snapshot();
+			let deferred_challengers = DeferredChallengers::<T>::get();
+			Self::clear_pending_election();
+
+			if Self::is_election_failed(&active_validator_set) {
+				weight.saturating_accrue(Self::retain_active_validator_set());
+				return weight;
+			}
+
+			let epoch_snapshot = match pending_snapshot {
+				Some(mut epoch_snapshot) => {
+					epoch_snapshot.retain_validators(
+						&active_validator_set
+							.iter()
+							.map(|(validator, _, _)| validator.clone())
+							.collect::<BTreeSet<_>>(),
+					);
+					epoch_snapshot
+				},
+				None => {
+					weight.saturating_accrue(Self::clear_epoch_delegations(next_epoch_index));
+					let mut epoch_snapshot = EpochSnapshot::<T>::default();
+					let total_delegations = Self::capture_snapshot_page(
+						next_epoch_index,
+						&mut epoch_snapshot,
+						&active_validator_set,
+					);
+					weight.saturating_accrue(<T as Config>::WeightInfo::capture_snapshot_page(
+						active_validator_set.len() as u32,
+						total_delegations,
+					));
+					epoch_snapshot
+				},
+			};
+
+			let rotation = Self::validator_set_rotation(&active_validator_set);
+			Self::install_active_validator_set(
+				next_epoch_index,
+				&active_validator_set,
+				epoch_snapshot,
+			);
+			Self::move_to_next_epoch(active_validator_set, rotation, deferred_challengers);
+			weight
+		}
+
+		/// Plans the validator set of the next session when the epochs are driven by
+		/// `pallet_session`. The elected set and its snapshot are stored as pending until the
+		/// session starts. Returns `None` if the election fails so that the current validator set
+		/// is kept. If an emergency set was installed since the last planned session, it is
+		/// planned instead of an elected one.
+		pub(crate) fn plan_session_validator_set() -> Option<Vec<T::AccountId>> {
+			Self::clear_pending_election();
+			if PendingEmergencySession::<T>::take() {
+				return Some(Self::store_session_validator_set(
+					CurrentActiveValidators::<T>::get().to_vec(),
+				));
+			}
+			let active_validator_set = Self::select_active_validator_set();
+			if Self::is_election_failed(&active_validator_set) {
+				return None;
+			}
+			Some(Self::store_session_validator_set(active_validator_set))
+		}
+
+		/// Stores the validator set planned for the next session and its snapshot as pending.
+		/// Returns the planned validators
+		fn store_session_validator_set(
+			active_validator_set: CandidateDelegationSet<T>,
+		) -> Vec<T::AccountId> {
+			let next_epoch_index = EpochIndex::<T>::get().saturating_add(1);
+			Self::clear_epoch_delegations(next_epoch_index);
+			PendingSnapshotPages::<T>::insert(
+				0,
+				Self::capture_epoch_snapshot(next_epoch_index, &active_validator_set),
+			);
+			let new_set = active_validator_set
+				.iter()
+				.map(|(active_validator, _, _)| active_validator.clone())
+				.collect::<Vec<T::AccountId>>();
+			PendingValidatorSet::<T>::put(
+				BoundedVec::try_from(active_validator_set)
+					.expect("Exceed limit number of the validators in the active set"),
+			);
+			new_set
+		}
+
+		/// Installs the validator set planned for the started session and moves to the next
+		/// epoch. If the planning failed, the current active validator set is kept.
+		pub(crate) fn enact_session_validator_set() {
+			let pending_validator_set = PendingValidatorSet::<T>::get();
+			let pending_snapshot = Self::pending_epoch_snapshot();
+			let deferred_challengers = DeferredChallengers::<T>::get();
+			Self::clear_pending_election();
+
+			let (Some(active_validator_set), Some(epoch_snapshot)) =
+				(pending_validator_set, pending_snapshot)
+			else {
+				Self::retain_active_validator_set();
+				return;
+			};
+
+			let active_validator_set = active_validator_set.to_vec();
+			let rotation = Self::validator_set_rotation(&active_validator_set);
+			Self::install_active_validator_set(
+				EpochIndex::<T>::get().saturating_add(1),
+				&active_validator_set,
+				epoch_snapshot,
+			);
+			Self::move_to_next_epoch(active_validator_set, rotation, deferred_challengers);
+		}
+
+		/// Moves to the next epoch with the current active validator set when the election failed.
+		/// Not enough online candidates to form a new active validator set, the last good set is
+		/// kept so that the block production does not halt. The delegations of the kept set are
+		/// captured again for the next epoch
+		fn retain_active_validator_set() -> Weight {
+			let epoch_index = EpochIndex::<T>::get();
+			let next_epoch_index = epoch_index.saturating_add(1);
+			let active_validator_set = CurrentActiveValidators::<T>::get().to_vec();
+
+			let mut weight = Self::clear_epoch_delegations(next_epoch_index);
+			let mut epoch_snapshot = EpochSnapshot::<T>::default();
+			let total_delegations = Self::capture_snapshot_page(
+				next_epoch_index,
+				&mut epoch_snapshot,
+				&active_validator_set,
+			);
+			weight.saturating_accrue(<T as Config>::WeightInfo::capture_snapshot_page(
+				active_validator_set.len() as u32,
+				total_delegations,
+			));
+			Self::store_epoch_snapshot(next_epoch_index, epoch_snapshot);
+
+			Self::deposit_event(Event::ElectionFailed {
+				epoch: next_epoch_index,
+				online_candidates: Self::get_electable_candidate_set().len() as u32,
+				retained_validators: active_validator_set.len() as u32,
 			});
+			Self::move_to_next_epoch(active_validator_set, (Vec::new(), Vec::new()), 0);
+			weight
 		}
 
-		/// Filters top staked validators for the active set based on their stake (bond + total
-		/// delegations), ensuring the set does not exceed the configured maximum.
-		pub(crate) fn select_active_validator_set() -> CandidateDelegationSet<T> {
-			// If the number of candidates is below the threshold for active set, network won't
-			// function
-			if CandidatePool::<T>::count() < T::MinActiveValidators::get() {
-				return vec![];
+		/// Assembles the snapshot of the pending validator set from the pages captured so far
+		fn pending_epoch_snapshot() -> Option<EpochSnapshot<T>> {
+			let mut pages = PendingSnapshotPages::<T>::iter().collect::<Vec<_>>();
+			if pages.is_empty() {
+				return None;
 			}
-			let total_in_active_set = T::MaxActiveValidators::get();
-			if CandidatePool::<T>::count() < total_in_active_set {
-				// If the number of candidates does not reached the threshold, return all
-				return Self::get_online_candidate_set();
+			pages.sort_by_key(|(page_index, _)| *page_index);
+			let mut epoch_snapshot = EpochSnapshot::<T>::default();
+			for (_, page) in pages {
+				epoch_snapshot.append_page(page);
+			}
+			Some(epoch_snapshot)
+		}
+
+		/// Removes the state of the paged election
+		fn clear_pending_election() {
+			PagedElectionPhase::<T>::kill();
+			ElectableCandidates::<T>::kill();
+			let _ = ElectableCandidateDelegations::<T>::clear(T::MaxCandidates::get(), None);
+			PendingValidatorSet::<T>::kill();
+			let _ = PendingSnapshotPages::<T>::clear(T::MaxActiveValidators::get(), None);
+			SnapshotPageCursor::<T>::kill();
+			DeferredChallengers::<T>::kill();
+		}
+
+		/// Removes the delegations captured for the epoch by a snapshot capture that was discarded,
+		/// before the snapshot of the epoch is captured again
+		fn clear_epoch_delegations(epoch_index: u32) -> Weight {
+			let result = EpochDelegations::<T>::clear_prefix(
+				(epoch_index,),
+				MaxSnapshotDelegations::<T>::get(),
+				None,
+			);
+			T::DbWeight::get().reads_writes(result.loops.into(), result.unique.into())
+		}
+
+		/// Updates the active validator set of the current epoch, captures the epoch snapshot of
+		/// the new set and reports it to the external system. The unclaimed delegator rewards of
+		/// the replaced snapshot, if any, are settled first
+		pub(crate) fn set_active_validator_set(active_validator_set: &CandidateDelegationSet<T>) {
+			let epoch_index = EpochIndex::<T>::get();
+			if EpochSnapshots::<T>::contains_key(epoch_index) {
+				Self::settle_epoch_rewards(epoch_index);
+			}
+			Self::install_active_validator_set(
+				epoch_index,
+				active_validator_set,
+				Self::capture_epoch_snapshot(epoch_index, active_validator_set),
+			);
+		}
+
+		/// Updates the active validator set with the provided snapshot of the epoch and reports it
+		/// to the external system
+		fn install_active_validator_set(
+			epoch_index: u32,
+			active_validator_set: &CandidateDelegationSet<T>,
+			epoch_snapshot: EpochSnapshot<T>,
+		) {
+			CurrentActiveValidators::<T>::put(
+				BoundedVec::try_from(active_validator_set.to_vec())
+					.expect("Exceed limit number of the validators in the active set"),
+			);
+			Self::store_epoch_snapshot(epoch_index, epoch_snapshot);
+
+			let new_set = active_validator_set
+				.iter()
+				.map(|(active_validator, _, _)| active_validator.clone())
+				.collect::<Vec<T::AccountId>>();
+			Self::report_new_validators(new_set);
+		}
+
+		/// Stores the snapshot of the epoch and opens the reward pools of its validators
+		pub(crate) fn store_epoch_snapshot(epoch_index: u32, epoch_snapshot: EpochSnapshot<T>) {
+			// The commission of the validators applies to the whole epoch, within the bounds
+			let (min_commission, max_commission) =
+				(MinCommission::<T>::get(), MaxCommission::<T>::get());
+			for (validator, bond) in epoch_snapshot.validators.iter() {
+				let commission = CandidatePool::<T>::get(validator)
+					.map(|candidate_detail| candidate_detail.commission)
+					.unwrap_or_default()
+					.clamp(min_commission, max_commission);
+				ValidatorRewardPools::<T>::insert(
+					epoch_index,
+					validator,
+					ValidatorRewardPool::new(
+						*bond,
+						epoch_snapshot.total_delegations_of(validator),
+						commission,
+					),
+				);
 			}
-			// Collect candidates with their total stake (bond + total delegations)
-			let mut sorted_candidates: CandidateDelegationSet<T> = Self::get_online_candidate_set();
+			EpochSnapshots::<T>::insert(epoch_index, epoch_snapshot);
+		}
 
-			// Sort candidates by their total stake in descending order
-			sorted_candidates.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+		/// Get the delegation edges `(delegator, candidate, amount)` of the provided candidates
+		pub fn get_delegation_edges(candidates: &CandidateDelegationSet<T>) -> DelegationEdges<T> {
+			let mut edges: DelegationEdges<T> = vec![];
+			for (candidate, _, _) in candidates.iter() {
+				for (delegator, amount) in Self::get_candidate_delegations(candidate) {
+					edges.push((delegator, candidate.clone(), amount));
+				}
+			}
+			edges
+		}
 
-			// Select the top candidates based on the maximum active validators allowed
-			let usize_total_in_active_set = total_in_active_set as usize;
-			sorted_candidates.into_iter().take(usize_total_in_active_set).collect()
+		/// Get the delegations `(delegator, amount)` of the candidate
+		pub fn get_candidate_delegations(
+			candidate: &T::AccountId,
+		) -> Vec<(T::AccountId, BalanceOf<T>)> {
+			CandidateDelegators::<T>::get(candidate)
+				.into_iter()
+				.filter_map(|delegator| {
+					let delegation_info = DelegationInfos::<T>::get(&delegator, candidate)?;
+					Some((delegator, delegation_info.amount))
+				})
+				.collect()
 		}
 
 		/// Get the candidate information associated with the delegations of the candidate
@@ -1202,14 +2918,92 @@ pub mod pallet {
 		pub fn get_online_candidate_set() -> CandidateDelegationSet<T> {
 			CandidatePool::<T>::iter()
 				.filter_map(|(candidate, candidate_detail)| match candidate_detail.status {
-					ValidatorStatus::Online => {
-						Some((candidate, candidate_detail.bond, candidate_detail.total()))
-					},
+					ValidatorStatus::Online =>
+						Some((candidate, candidate_detail.bond, candidate_detail.total())),
 					ValidatorStatus::Offline => None,
 				})
 				.collect()
 		}
 
+		/// Get the online candidates with the highest total stake from the sorted candidate index,
+		/// bounded by `MaxElectableCandidates`. Bags are read from the highest threshold and a bag
+		/// is always read entirely, so no candidate with a higher stake is left out
+		pub fn get_electable_candidate_set() -> CandidateDelegationSet<T> {
+			let max_electable = T::MaxElectableCandidates::get() as usize;
+			let mut electable_candidates: CandidateDelegationSet<T> = vec![];
+			for threshold in Self::bag_thresholds().into_iter().rev() {
+				if electable_candidates.len() >= max_electable {
+					break;
+				}
+				for candidate in CandidateBags::<T>::get(threshold) {
+					if let Some(candidate_detail) = CandidatePool::<T>::get(&candidate) {
+						if candidate_detail.status == ValidatorStatus::Online &&
+							Self::has_session_keys(&candidate)
+						{
+							electable_candidates.push((
+								candidate,
+								candidate_detail.bond,
+								candidate_detail.total(),
+							));
+						}
+					}
+				}
+			}
+			electable_candidates.sort_by_key(|&(_, _, total_stake)| Reverse(total_stake));
+			electable_candidates.truncate(max_electable);
+			electable_candidates
+		}
+
+		/// The upper thresholds of all bags in ascending order, including the last bag for the
+		/// candidates above the highest configured threshold
+		pub fn bag_thresholds() -> Vec<BalanceOf<T>> {
+			let mut thresholds = T::BagThresholds::get().to_vec();
+			if thresholds.last() != Some(&BalanceOf::<T>::max_value()) {
+				thresholds.push(BalanceOf::<T>::max_value());
+			}
+			thresholds
+		}
+
+		/// The upper threshold of the bag for the provided total stake
+		pub fn bag_threshold_of(total_stake: BalanceOf<T>) -> BalanceOf<T> {
+			T::BagThresholds::get()
+				.iter()
+				.find(|threshold| total_stake <= **threshold)
+				.copied()
+				.unwrap_or(BalanceOf::<T>::max_value())
+		}
+
+		/// Moves the candidate into the bag matching its current total stake
+		pub(crate) fn update_candidate_bag(candidate: &T::AccountId) {
+			let Some(candidate_detail) = CandidatePool::<T>::get(candidate) else {
+				return Self::remove_candidate_from_bag(candidate);
+			};
+			let threshold = Self::bag_threshold_of(candidate_detail.total());
+			match CandidateBagOf::<T>::get(candidate) {
+				Some(current_threshold) if current_threshold == threshold => return,
+				Some(_) => Self::remove_candidate_from_bag(candidate),
+				None => {},
+			}
+			CandidateBags::<T>::mutate(threshold, |bag| {
+				bag.try_push(candidate.clone())
+					.expect("Bag is bounded by the maximum number of candidates")
+			});
+			CandidateBagOf::<T>::insert(candidate, threshold);
+		}
+
+		/// Removes the candidate from the sorted candidate index
+		pub(crate) fn remove_candidate_from_bag(candidate: &T::AccountId) {
+			if let Some(threshold) = CandidateBagOf::<T>::take(candidate) {
+				let mut bag = CandidateBags::<T>::get(threshold);
+				bag.retain(|member| member != candidate);
+				if bag.is_empty() {
+					CandidateBags::<T>::remove(threshold);
+				} else {
+					CandidateBags::<T>::insert(threshold, bag);
+				}
+			}
+		}
+
 		/// Reporting new validator set to the external system
 		pub fn report_new_validators(new_set: Vec<T::AccountId>) {
 			T::ReportNewValidatorSet::report_new_validator_set(new_set);
@@ -1220,9 +3014,10 @@ pub mod pallet {
 			candidate: &T::AccountId,
 			amount: &BalanceOf<T>,
 		) -> DispatchResultWithValue<BalanceOf<T>> {
-			let mut candidate_detail = Self::get_candidate(&candidate)?;
+			let mut candidate_detail = Self::get_candidate(candidate)?;
 			let total_delegated_amount = candidate_detail.sub_delegated_amount(*amount)?;
-			CandidatePool::<T>::set(&candidate, Some(candidate_detail));
+			CandidatePool::<T>::set(candidate, Some(candidate_detail));
+			Self::update_candidate_bag(candidate);
 
 			Ok(total_delegated_amount)
 		}
@@ -1232,9 +3027,10 @@ pub mod pallet {
 			candidate: &T::AccountId,
 			amount: &BalanceOf<T>,
 		) -> DispatchResultWithValue<BalanceOf<T>> {
-			let mut candidate_detail = Self::get_candidate(&candidate)?;
+			let mut candidate_detail = Self::get_candidate(candidate)?;
 			let total_delegated_amount = candidate_detail.add_delegated_amount(*amount)?;
-			CandidatePool::<T>::set(&candidate, Some(candidate_detail));
+			CandidatePool::<T>::set(candidate, Some(candidate_detail));
+			Self::update_candidate_bag(candidate);
 
 			Ok(total_delegated_amount)
 		}
@@ -1244,18 +3040,18 @@ pub mod pallet {
 			delegator: &T::AccountId,
 			candidate: &T::AccountId,
 		) -> DispatchResult {
-			DelegationInfos::<T>::remove(&delegator, &candidate);
+			DelegationInfos::<T>::remove(delegator, candidate);
 
-			let delegate_count = DelegateCountMap::<T>::get(&delegator);
-			DelegateCountMap::<T>::set(&delegator, delegate_count.saturating_sub(1));
+			let delegate_count = DelegateCountMap::<T>::get(delegator);
+			DelegateCountMap::<T>::set(delegator, delegate_count.saturating_sub(1));
 
 			// Remove delegator from the candidate delegators vector
-			let mut candidate_delegators = CandidateDelegators::<T>::get(&candidate);
+			let mut candidate_delegators = CandidateDelegators::<T>::get(candidate);
 			candidate_delegators
-				.binary_search(&delegator)
+				.binary_search(delegator)
 				.map_err(|_| Error::<T>::DelegationDoesNotExist)
 				.map(|indx| candidate_delegators.remove(indx))?;
-			CandidateDelegators::<T>::set(&candidate, candidate_delegators);
+			CandidateDelegators::<T>::set(candidate, candidate_delegators);
 
 			Ok(())
 		}
@@ -1287,14 +3083,19 @@ pub mod pallet {
 			request_by: T::AccountId,
 			action_type: DelayActionType,
 		) -> DispatchResult {
-			let now = frame::deps::frame_system::Pallet::<T>::block_number();
 			let request = DelayActionRequests::<T>::get(&request_by, &action_type)
 				.ok_or(Error::<T>::NoDelayActionRequestFound)?;
 			// Delay action is due, start executing the action
-			ensure!(
-				now.saturating_sub(request.created_at) >= request.delay_for,
-				Error::<T>::ActionIsStillInDelayDuration
-			);
+			let is_due = match request.created_at_time {
+				Some(created_at_time) =>
+					T::Time::now().saturating_sub(created_at_time) >=
+						Self::blocks_to_time(request.delay_for),
+				None => {
+					let now = frame::deps::frame_system::Pallet::<T>::block_number();
+					now.saturating_sub(request.created_at) >= request.delay_for
+				},
+			};
+			ensure!(is_due, Error::<T>::ActionIsStillInDelayDuration);
 
 			match action_type {
 				DelayActionType::CandidateLeaved => {
@@ -1328,6 +3129,8 @@ pub mod pallet {
 					target,
 					created_at: frame::deps::frame_system::Pallet::<T>::block_number(),
 					delay_for: Self::get_delay_action_duration(&action_type),
+					created_at_time: (T::EpochTrigger::get() == EpochTrigger::Timestamp)
+						.then(T::Time::now),
 					amount: consumed_amount,
 				}),
 			);
@@ -1355,15 +3158,17 @@ pub mod pallet {
 			let candidate_detail = Self::get_candidate(&candidate)?;
 			Self::release_candidate_bonds(&candidate, candidate_detail.bond)?;
 
-			// If there are reward points when candidate leaves the pool, send it to them
+			// Removing any information related the registration of the candidate in the pool
+			CandidatePool::<T>::remove(&candidate);
+			Self::remove_candidate_from_bag(&candidate);
+
+			// If there are reward points when candidate leaves the pool, send it to them. The
+			// reward points are kept to be claimed later if the reward source is insufficient
 			let reward_points = RewardPoints::<T>::get(&candidate);
 			if reward_points > Zero::zero() {
-				Self::claim_reward_inner(candidate.clone(), reward_points);
+				let _ = Self::claim_reward_inner(candidate.clone(), reward_points, None);
 			}
 
-			// Removing any information related the registration of the candidate in the pool
-			CandidatePool::<T>::remove(&candidate);
-
 			Self::deposit_event(Event::CandidateRegistrationRemoved { candidate_id: candidate });
 
 			Ok(())
@@ -1417,11 +3222,11 @@ pub mod pallet {
 			candidate: &T::AccountId,
 			delegator: &T::AccountId,
 		) -> DispatchResult {
-			let mut candidate_delegators = CandidateDelegators::<T>::get(&candidate);
+			let mut candidate_delegators = CandidateDelegators::<T>::get(candidate);
 			candidate_delegators
 				.try_push(delegator.clone())
 				.map_err(|_| Error::<T>::TooManyDelegatorsInPool)?;
-			CandidateDelegators::<T>::set(&candidate, candidate_delegators);
+			CandidateDelegators::<T>::set(candidate, candidate_delegators);
 			Ok(())
 		}
 
@@ -1429,19 +3234,34 @@ pub mod pallet {
 			delegator: &T::AccountId,
 			candidate: &T::AccountId,
 		) -> DispatchResultWithValue<DelegationInfo<T>> {
-			Ok(DelegationInfos::<T>::try_get(&delegator, &candidate)
+			Ok(DelegationInfos::<T>::try_get(delegator, candidate)
 				.map_err(|_| Error::<T>::DelegationDoesNotExist)?)
 		}
 
 		pub fn get_candidate(
 			candidate: &T::AccountId,
 		) -> DispatchResultWithValue<CandidateDetail<T>> {
-			Ok(CandidatePool::<T>::try_get(&candidate)
+			Ok(CandidatePool::<T>::try_get(candidate)
 				.map_err(|_| Error::<T>::CandidateDoesNotExist)?)
 		}
 
 		pub fn is_candidate(validator: &T::AccountId) -> bool {
-			CandidatePool::<T>::contains_key(&validator)
+			CandidatePool::<T>::contains_key(validator)
+		}
+
+		/// Whether the account can author blocks. The session keys are only required with
+		/// [`EpochTrigger::Session`]
+		pub fn has_session_keys(validator: &T::AccountId) -> bool {
+			if T::EpochTrigger::get() != EpochTrigger::Session {
+				return true;
+			}
+			T::ValidatorIdOf::convert(validator.clone())
+				.is_some_and(|validator_id| T::ValidatorRegistration::is_registered(&validator_id))
+		}
+
+		pub fn is_online_candidate(validator: &T::AccountId) -> bool {
+			CandidatePool::<T>::get(validator)
+				.is_some_and(|candidate_detail| candidate_detail.status == ValidatorStatus::Online)
 		}
 
 		/// Core logic to register candidate
@@ -1454,10 +3274,11 @@ pub mod pallet {
 				Error::<T>::TooManyValidators
 			);
 			// Hold the amount for candidate bond registration
-			T::NativeBalance::hold(&HoldReason::CandidateBondReserved.into(), &validator, bond)?;
+			T::NativeBalance::hold(&HoldReason::CandidateBondReserved.into(), validator, bond)?;
 
 			// Store the amount held in our local storage.
-			CandidatePool::<T>::insert(&validator, CandidateDetail::new(bond));
+			CandidatePool::<T>::insert(validator, CandidateDetail::new(bond));
+			Self::update_candidate_bag(validator);
 			Ok(())
 		}
 
@@ -1478,7 +3299,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::NativeBalance::release(
 				&HoldReason::CandidateBondReserved.into(),
-				&candidate,
+				candidate,
 				bond,
 				Precision::BestEffort,
 			)?;
@@ -1492,84 +3313,524 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::NativeBalance::release(
 				&HoldReason::DelegateAmountReserved.into(),
-				&delegator,
+				delegator,
 				*amount,
 				Precision::BestEffort,
 			)?;
 			Ok(())
 		}
 
-		pub(crate) fn calculate_reward(total: BalanceOf<T>, percent: u32) -> BalanceOf<T> {
-			Percent::from_rational(percent, 100)
-				* Percent::from_rational(BalanceRate::<T>::get(), 1000)
-				* total
+		/// The rewards of a block for the stake of the block author, its bond and the total
+		/// delegated to it, at the balance rate
+		pub(crate) fn block_reward(total_stake: BalanceOf<T>) -> BalanceOf<T> {
+			Perbill::from_rational(BalanceRate::<T>::get(), 1000) * total_stake
 		}
 
 		/// Captures an epoch snapshot containing information about the active validators and their
 		/// delegations. It iterates over the provided active validator set to populate the snapshot
-		/// with validator IDs, their bonded amounts, and delegator details including the amount
-		/// delegated. This function constructs and returns an EpochSnapshot object populated with
-		/// the gathered data.
+		/// with validator IDs, their bonded amounts, and the total delegated to them. The
+		/// delegations are stored in `EpochDelegations` under the provided epoch index. This
+		/// function constructs and returns an EpochSnapshot object populated with the gathered
+		/// data.
 		pub fn capture_epoch_snapshot(
+			epoch_index: u32,
 			active_validator_set: &CandidateDelegationSet<T>,
 		) -> EpochSnapshot<T> {
 			let mut epoch_snapshot = EpochSnapshot::<T>::default();
-			for (active_validator_id, bond, _) in active_validator_set.to_vec().iter() {
-				epoch_snapshot.add_validator(active_validator_id.clone(), bond.clone());
+			Self::capture_snapshot_page(epoch_index, &mut epoch_snapshot, active_validator_set);
+			epoch_snapshot
+		}
+
+		/// Adds the provided validators to the epoch snapshot and stores their delegations under
+		/// the epoch index. Returns the number of delegations read
+		pub(crate) fn capture_snapshot_page(
+			epoch_index: u32,
+			epoch_snapshot: &mut EpochSnapshot<T>,
+			validators: &[(T::AccountId, BalanceOf<T>, BalanceOf<T>)],
+		) -> u32 {
+			let mut total_delegations = 0u32;
+			for (active_validator_id, bond, _) in validators.iter() {
+				epoch_snapshot.add_validator(active_validator_id.clone(), *bond);
 				for delegator in CandidateDelegators::<T>::get(active_validator_id) {
+					total_delegations.saturating_inc();
 					if let Some(delegation_info) =
-						DelegationInfos::<T>::get(&delegator, &active_validator_id)
+						DelegationInfos::<T>::get(&delegator, active_validator_id)
 					{
-						epoch_snapshot.add_delegator(
-							delegator,
-							active_validator_id.clone(),
+						EpochDelegations::<T>::insert(
+							(epoch_index, &delegator, active_validator_id),
 							delegation_info.amount,
 						);
+						epoch_snapshot
+							.add_delegation(active_validator_id.clone(), delegation_info.amount);
 					}
 				}
 			}
-			epoch_snapshot
+			total_delegations
+		}
+
+		/// Rewards the author of the current block unless the block is rewarded already
+		fn reward_block_author_once(block_author: &T::AccountId) {
+			let now = frame::deps::frame_system::Pallet::<T>::block_number();
+			if LastRewardedBlock::<T>::get() == Some(now) {
+				return;
+			}
+			LastRewardedBlock::<T>::put(now);
+			Self::reward_block_author(block_author);
+		}
+
+		/// Credits the rewards of the block produced by the author to the validator and accrues
+		/// the rewards of its delegators in the reward pool of the epoch, or adds a point to the
+		/// validator with `RewardModel::EpochPoints`. The cost does not depend on the number of
+		/// delegators
+		pub(crate) fn reward_block_author(block_author: &T::AccountId) {
+			// The rewards are calculated with the amounts captured in the snapshot of the epoch
+			let epoch_index = EpochIndex::<T>::get();
+			let Some(mut reward_pool) = ValidatorRewardPools::<T>::get(epoch_index, block_author)
+			else {
+				return;
+			};
+			if T::RewardModel::get() == RewardModel::EpochPoints {
+				EpochRewardPoints::<T>::mutate(epoch_index, |epoch_points| {
+					epoch_points.add_point(block_author)
+				});
+				return;
+			}
+
+			let block_reward =
+				Self::block_reward(reward_pool.bond.saturating_add(reward_pool.total_delegations));
+			let delegator_rewards = reward_pool.share(block_reward);
+			ValidatorRewardPools::<T>::insert(epoch_index, block_author, reward_pool);
+			let validator_rewards = block_reward.saturating_sub(delegator_rewards);
+			Self::credit_reward_points(block_author, validator_rewards, epoch_index);
+
+			EpochHistory::<T>::mutate(epoch_index, |epoch_info| {
+				if let Some(epoch_info) = epoch_info {
+					epoch_info.add_rewards(block_reward);
+				}
+			});
+		}
+
+		/// Allocates the reward budget to the ended epoch, to be split between the validators that
+		/// earned points in the epoch
+		fn allocate_epoch_reward(epoch_index: u32) {
+			if EpochRewardPoints::<T>::get(epoch_index).total.is_zero() {
+				return;
+			}
+			EpochHistory::<T>::mutate(epoch_index, |epoch_info| {
+				if let Some(epoch_info) = epoch_info {
+					let reward_budget = Self::epoch_reward_budget(epoch_index, epoch_info);
+					epoch_info.add_rewards(reward_budget);
+				}
+			});
+		}
+
+		/// The reward budget of the epoch. With an inflation curve, the budget is computed from
+		/// the total issuance, the total staked in the epoch snapshot and the planned duration of
+		/// the epoch. Otherwise the budget is the fixed `EpochReward`
+		pub fn epoch_reward_budget(epoch_index: u32, epoch_info: &EpochInfo<T>) -> BalanceOf<T> {
+			let Some(inflation_curve) = ConfiguredInflationCurve::<T>::get() else {
+				return T::EpochReward::get();
+			};
+			let total_staked = EpochSnapshots::<T>::get(epoch_index)
+				.map(|epoch_snapshot| epoch_snapshot.total_staked())
+				.unwrap_or_default();
+			let epoch_time = Self::blocks_to_time(
+				epoch_info.planned_end_block.saturating_sub(epoch_info.start_block),
+			)
+			.unique_saturated_into();
+			inflation_curve.epoch_budget(
+				total_staked,
+				<T::NativeBalance as fungible::Inspect<T::AccountId>>::total_issuance(),
+				epoch_time,
+			)
+		}
+
+		pub(crate) fn do_payout_epoch(validator: T::AccountId, epoch_index: u32) -> DispatchResult {
+			ensure!(epoch_index < EpochIndex::<T>::get(), Error::<T>::EpochNotEnded);
+			ensure!(
+				!EpochPayouts::<T>::contains_key(epoch_index, &validator),
+				Error::<T>::EpochAlreadyPaidOut
+			);
+			let epoch_info =
+				EpochHistory::<T>::get(epoch_index).ok_or(Error::<T>::NoEpochRewardFound)?;
+			let epoch_points = EpochRewardPoints::<T>::get(epoch_index);
+			let points = epoch_points.points_of(&validator);
+			ensure!(
+				!points.is_zero() && !epoch_info.total_rewards.is_zero(),
+				Error::<T>::NoEpochRewardFound
+			);
+
+			let total_reward =
+				Perbill::from_rational(points, epoch_points.total) * epoch_info.total_rewards;
+			// The remainder of the rounding goes to the validator
+			let delegator_rewards = match ValidatorRewardPools::<T>::get(epoch_index, &validator) {
+				Some(mut reward_pool) => {
+					let delegator_rewards = reward_pool.share(total_reward);
+					ValidatorRewardPools::<T>::insert(epoch_index, &validator, reward_pool);
+					delegator_rewards
+				},
+				None => Zero::zero(),
+			};
+			let validator_reward = total_reward.saturating_sub(delegator_rewards);
+			Self::credit_reward_points(&validator, validator_reward, epoch_index);
+			EpochPayouts::<T>::insert(epoch_index, &validator, total_reward);
+
+			Self::deposit_event(Event::EpochPaidOut {
+				epoch: epoch_index,
+				validator,
+				validator_reward,
+				delegator_rewards,
+			});
+			Ok(())
+		}
+
+		/// The total claimable rewards of the account, including the delegator rewards that are
+		/// not settled yet in the epochs kept in the history
+		pub fn reward_points(who: T::AccountId) -> BalanceOf<T> {
+			Self::delegator_reward_claims(&who)
+				.0
+				.into_iter()
+				.fold(RewardPoints::<T>::get(&who), |total, (_, _, _, rewards)| {
+					total.saturating_add(rewards)
+				})
+		}
+
+		/// The maximum number of delegations read to settle the rewards of a delegator, a
+		/// delegation to each of `MaxDelegateCount` validators in every epoch kept in the history
+		pub fn max_delegator_reward_claims() -> u32 {
+			T::HistoryDepth::get()
+				.saturating_add(1)
+				.saturating_mul(T::MaxDelegateCount::get())
+		}
+
+		/// The unclaimed rewards of the delegator in the reward pools of the epochs kept in the
+		/// history, along with the number of delegations read
+		fn delegator_reward_claims(
+			delegator: &T::AccountId,
+		) -> (Vec<DelegatorRewardClaim<T>>, u32) {
+			let current_epoch_index = EpochIndex::<T>::get();
+			let mut claims = vec![];
+			let mut total_delegations = 0u32;
+			for epoch_index in
+				current_epoch_index.saturating_sub(T::HistoryDepth::get())..=current_epoch_index
+			{
+				for (validator, amount) in
+					EpochDelegations::<T>::iter_prefix((epoch_index, delegator))
+				{
+					total_delegations.saturating_inc();
+					let Some(reward_pool) = ValidatorRewardPools::<T>::get(epoch_index, &validator)
+					else {
+						continue;
+					};
+					let claimed =
+						ClaimedRewardPerStake::<T>::get((epoch_index, delegator, &validator));
+					let rewards = reward_pool.rewards_of(amount, claimed);
+					if !rewards.is_zero() {
+						claims.push((
+							epoch_index,
+							validator,
+							reward_pool.reward_per_stake,
+							rewards,
+						));
+					}
+				}
+			}
+			(claims, total_delegations)
+		}
+
+		/// Moves the unclaimed rewards of the delegator from the reward pools into its reward
+		/// points. Returns the number of delegations read
+		fn settle_delegator_rewards(delegator: &T::AccountId) -> u32 {
+			let (claims, total_delegations) = Self::delegator_reward_claims(delegator);
+			for (epoch_index, validator, reward_per_stake, rewards) in claims {
+				ClaimedRewardPerStake::<T>::insert(
+					(epoch_index, delegator, validator),
+					reward_per_stake,
+				);
+				Self::credit_reward_points(delegator, rewards, epoch_index);
+			}
+			total_delegations
 		}
 
-		/// This function updates the reward points for a validator and its delegators based on
-		/// block production rewards.
-		pub fn sync_validator_rewards(
+		/// Moves the unclaimed rewards of the delegation into the reward points of the delegator
+		/// and removes the claimed reward of the delegation
+		fn settle_delegation(
+			epoch_index: u32,
+			delegator: &T::AccountId,
 			validator: &T::AccountId,
-			delegations: &BTreeMap<(T::AccountId, T::AccountId), BalanceOf<T>>,
-			total_bond: &BalanceOf<T>,
+			amount: BalanceOf<T>,
 		) {
-			// Calculating the new reward of the block author
-			let mut rewards = RewardPoints::<T>::get(&validator);
-			rewards = rewards
-				.saturating_add(Self::calculate_reward(*total_bond, T::AuthorCommission::get()));
-			RewardPoints::<T>::set(validator.clone(), rewards);
-
-			for ((delegator, candidate), amount) in delegations.iter() {
-				if candidate != validator {
-					continue;
+			let claimed = ClaimedRewardPerStake::<T>::take((epoch_index, delegator, validator));
+			let Some(reward_pool) = ValidatorRewardPools::<T>::get(epoch_index, validator) else {
+				return;
+			};
+			let rewards = reward_pool.rewards_of(amount, claimed);
+			if !rewards.is_zero() {
+				Self::credit_reward_points(delegator, rewards, epoch_index);
+			}
+		}
+
+		/// Moves the unclaimed rewards of all the delegators of the epoch into their reward points
+		/// and closes the reward pools of the epoch. Only used when the snapshot of the current
+		/// epoch is replaced, the pruned epochs are settled page by page in `on_idle`
+		fn settle_epoch_rewards(epoch_index: u32) {
+			for ((delegator, validator), amount) in
+				EpochDelegations::<T>::drain_prefix((epoch_index,))
+			{
+				Self::settle_delegation(epoch_index, &delegator, &validator, amount);
+			}
+			let _ = ValidatorRewardPools::<T>::clear_prefix(
+				epoch_index,
+				T::MaxActiveValidators::get(),
+				None,
+			);
+		}
+
+		/// Settles the delegator rewards of the epochs pruned from the history, starting from the
+		/// oldest one. The reward pools of an epoch are closed once all its delegations are
+		/// settled. The number of delegations settled is bounded by the remaining weight
+		pub(crate) fn settle_pruned_rewards(remaining_weight: Weight) -> Weight {
+			let base_weight = <T as Config>::WeightInfo::settle_pruned_rewards(0, 0);
+			let first_kept_epoch = EpochIndex::<T>::get().saturating_sub(T::HistoryDepth::get());
+			let mut epoch_index = OldestUnsettledEpoch::<T>::get();
+			if epoch_index >= first_kept_epoch || remaining_weight.any_lt(base_weight) {
+				return Weight::zero();
+			}
+			let delegation_weight =
+				<T as Config>::WeightInfo::settle_pruned_rewards(1, 0).saturating_sub(base_weight);
+			let close_weight =
+				<T as Config>::WeightInfo::settle_pruned_rewards(0, T::MaxActiveValidators::get())
+					.saturating_sub(base_weight);
+
+			let mut weight = base_weight;
+			while epoch_index < first_kept_epoch {
+				// The weight to close the reward pools of the epoch is reserved
+				let max_delegations = remaining_weight
+					.checked_sub(&weight.saturating_add(close_weight))
+					.and_then(|weight| weight.checked_div_per_component(&delegation_weight))
+					.unwrap_or_default();
+				if max_delegations.is_zero() {
+					break;
+				}
+
+				let delegations = EpochDelegations::<T>::iter_prefix((epoch_index,))
+					.take(max_delegations.min(u32::MAX as u64) as usize)
+					.collect::<Vec<_>>();
+				for ((delegator, validator), amount) in delegations.iter() {
+					Self::settle_delegation(epoch_index, delegator, validator, *amount);
+					EpochDelegations::<T>::remove((epoch_index, delegator, validator));
+				}
+				weight
+					.saturating_accrue(delegation_weight.saturating_mul(delegations.len() as u64));
+				if (delegations.len() as u64) == max_delegations {
+					break;
+				}
+
+				// All the delegations of the epoch are settled
+				let _ = ValidatorRewardPools::<T>::clear_prefix(
+					epoch_index,
+					T::MaxActiveValidators::get(),
+					None,
+				);
+				weight.saturating_accrue(close_weight);
+				epoch_index.saturating_inc();
+			}
+			OldestUnsettledEpoch::<T>::put(epoch_index);
+			weight
+		}
+
+		/// Adds the rewards to the reward points of the account. The most recent epoch in which
+		/// the account earned rewards is recorded as the start of the expiry of its reward points
+		fn credit_reward_points(who: &T::AccountId, rewards: BalanceOf<T>, earned_epoch: u32) {
+			RewardPoints::<T>::mutate(who, |reward_points| {
+				*reward_points = reward_points.saturating_add(rewards)
+			});
+			RewardLastEarned::<T>::mutate(who, |last_earned| {
+				*last_earned = Some(last_earned.map_or(earned_epoch, |last| last.max(earned_epoch)))
+			});
+		}
+
+		/// Checks the accounts with unclaimed rewards, resuming after the last account checked by
+		/// the previous sweep, and forfeits the rewards that expired. The number of accounts
+		/// checked is bounded by the remaining weight
+		pub(crate) fn sweep_expired_rewards(remaining_weight: Weight) -> Weight {
+			let expiry_epochs = T::RewardExpiryEpochs::get();
+			let base_weight = <T as Config>::WeightInfo::sweep_expired_rewards(0);
+			let account_weight =
+				<T as Config>::WeightInfo::sweep_expired_rewards(1).saturating_sub(base_weight);
+			let max_accounts = remaining_weight
+				.checked_sub(&base_weight)
+				.and_then(|weight| weight.checked_div_per_component(&account_weight))
+				.unwrap_or_default()
+				.min(u32::MAX as u64) as u32;
+			if expiry_epochs.is_zero() || max_accounts.is_zero() {
+				return Weight::zero();
+			}
+
+			let accounts: Vec<(T::AccountId, u32)> = match RewardExpiryCursor::<T>::get() {
+				Some(cursor) =>
+					RewardLastEarned::<T>::iter_from(RewardLastEarned::<T>::hashed_key_for(cursor))
+						.take(max_accounts as usize)
+						.collect(),
+				None => RewardLastEarned::<T>::iter().take(max_accounts as usize).collect(),
+			};
+			// The sweep starts over once all the accounts are checked
+			match accounts.last() {
+				Some((who, _)) if accounts.len() == max_accounts as usize =>
+					RewardExpiryCursor::<T>::put(who),
+				_ => RewardExpiryCursor::<T>::kill(),
+			}
+
+			let current_epoch = EpochIndex::<T>::get();
+			for (who, last_earned) in accounts.iter() {
+				if current_epoch.saturating_sub(*last_earned) >= expiry_epochs {
+					Self::expire_rewards(who);
 				}
-				// Calculating the new reward of the block author
-				let mut rewards = RewardPoints::<T>::get(&delegator);
-				rewards = rewards
-					.saturating_add(Self::calculate_reward(*amount, T::DelegatorCommission::get()));
-				RewardPoints::<T>::set(delegator, rewards);
 			}
+			<T as Config>::WeightInfo::sweep_expired_rewards(accounts.len() as u32)
+		}
+
+		/// Forfeits the unclaimed reward points of the account to the `ExpiredRewardDestination`
+		fn expire_rewards(who: &T::AccountId) {
+			RewardLastEarned::<T>::remove(who);
+			let amount = RewardPoints::<T>::take(who);
+			if amount.is_zero() {
+				return;
+			}
+
+			let destination = T::ExpiredRewardDestination::get();
+			let result = match &destination {
+				ExpiredRewardDestination::Burn => Self::burn_reward(amount),
+				ExpiredRewardDestination::Treasury(treasury) =>
+					with_storage_layer(|| Self::pay_reward(treasury, amount)),
+			};
+			match result {
+				Ok(()) => Self::deposit_event(Event::RewardExpired {
+					who: who.clone(),
+					amount,
+					destination,
+				}),
+				Err(error) => Self::deposit_event(Event::RewardExpiryFailed {
+					who: who.clone(),
+					amount,
+					error,
+				}),
+			}
+		}
+
+		/// Burns the rewards from the `RewardSource`, the source account is kept alive. Nothing is
+		/// burnt with `RewardSource::Mint` as the rewards are not minted until they are claimed
+		fn burn_reward(amount: BalanceOf<T>) -> DispatchResult {
+			let source = match T::RewardSource::get() {
+				RewardSource::Mint => return Ok(()),
+				RewardSource::Pot => Self::reward_pot_account(),
+				RewardSource::Treasury(treasury) => treasury,
+			};
+			T::NativeBalance::burn_from(
+				&source,
+				amount,
+				Preservation::Preserve,
+				Precision::Exact,
+				Fortitude::Polite,
+			)
+			.map_err(|_| Error::<T>::InsufficientRewardSource)?;
+			Ok(())
 		}
 
-		fn claim_reward_inner(claimer: T::AccountId, reward_points: BalanceOf<T>) {
-			let _ = T::NativeBalance::mint_into(&claimer, reward_points);
+		/// Pays the reward points of the claimer to its reward destination. The rewards are paid
+		/// out on behalf of the claimer when `paid_by` is provided
+		fn claim_reward_inner(
+			claimer: T::AccountId,
+			reward_points: BalanceOf<T>,
+			paid_by: Option<T::AccountId>,
+		) -> DispatchResult {
+			let reward_destination = RewardDestinations::<T>::get(&claimer);
+			let payee = match &reward_destination {
+				RewardDestination::Account(payee) => payee.clone(),
+				_ => claimer.clone(),
+			};
+			Self::pay_reward(&payee, reward_points)?;
 
 			RewardPoints::<T>::remove(&claimer);
+			RewardLastEarned::<T>::remove(&claimer);
+
+			Self::deposit_event(match paid_by {
+				Some(paid_by) => Event::RewardPaidOut {
+					who: claimer.clone(),
+					paid_by,
+					total_reward: reward_points,
+				},
+				None =>
+					Event::RewardClaimed { claimer: claimer.clone(), total_reward: reward_points },
+			});
+
+			// The rewards stay in the free balance when they can not be staked
+			if reward_destination == RewardDestination::Staked {
+				if let Ok(candidate_id) =
+					with_storage_layer(|| Self::stake_reward(&claimer, reward_points))
+				{
+					Self::deposit_event(Event::RewardStaked {
+						who: claimer,
+						candidate_id,
+						amount: reward_points,
+					});
+				}
+			}
+			Ok(())
+		}
+
+		/// Adds the rewards paid into the account to its candidate bond, or to its largest
+		/// delegation when the account is not a candidate. Returns the candidate the rewards are
+		/// staked with
+		fn stake_reward(
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<T::AccountId, DispatchError> {
+			if let Some(mut candidate_detail) = CandidatePool::<T>::get(who) {
+				T::NativeBalance::hold(&HoldReason::CandidateBondReserved.into(), who, amount)?;
+				candidate_detail.update_bond(candidate_detail.bond.saturating_add(amount));
+				CandidatePool::<T>::insert(who, candidate_detail);
+				Self::update_candidate_bag(who);
+				return Ok(who.clone());
+			}
+
+			let (candidate, mut delegation_info) = DelegationInfos::<T>::iter_prefix(who)
+				.max_by_key(|(_, delegation_info)| delegation_info.amount)
+				.ok_or(Error::<T>::DelegationDoesNotExist)?;
+			T::NativeBalance::hold(&HoldReason::DelegateAmountReserved.into(), who, amount)?;
+			delegation_info.update_delegated_amount(delegation_info.amount.saturating_add(amount));
+			DelegationInfos::<T>::insert(who, &candidate, delegation_info);
+			Self::increase_candidate_delegations(&candidate, &amount)?;
+			Ok(candidate)
+		}
+
+		/// Pays the rewards from the `RewardSource` into the account, the source account is kept
+		/// alive
+		fn pay_reward(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			let source = match T::RewardSource::get() {
+				RewardSource::Mint => {
+					T::NativeBalance::mint_into(who, amount)?;
+					return Ok(());
+				},
+				RewardSource::Pot => Self::reward_pot_account(),
+				RewardSource::Treasury(treasury) => treasury,
+			};
+			T::NativeBalance::transfer(&source, who, amount, Preservation::Preserve)
+				.map_err(|_| Error::<T>::InsufficientRewardSource)?;
+			Ok(())
+		}
 
-			Self::deposit_event(Event::RewardClaimed { claimer, total_reward: reward_points });
+		/// The account of the reward pot, derived from `PalletId`
+		pub fn reward_pot_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
 		}
 
 		// Slashing the candidate bond, if under the minimum bond, candidate will be removed from
 		// the pool
 		pub fn do_slash(who: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
-			ensure!(!Self::is_candidate(&who), Error::<T>::CandidateAlreadyExist);
+			ensure!(Self::is_candidate(&who), Error::<T>::CandidateDoesNotExist);
 
-			let left_amount = T::NativeBalance::burn_held(
+			let slashed_amount = T::NativeBalance::burn_held(
 				&HoldReason::CandidateBondReserved.into(),
 				&who,
 				amount,
@@ -1577,22 +3838,23 @@ pub mod pallet {
 				Fortitude::Force,
 			)?;
 
-			let _ = T::OnSlashHandler::on_slash(&who, amount);
+			T::OnSlashHandler::on_slash(&who, slashed_amount);
 
 			let mut candidate_detail = Self::get_candidate(&who)?;
+			let left_amount = candidate_detail.bond.saturating_sub(slashed_amount);
+
+			Self::deposit_event(Event::CandidateBondSlashed {
+				candidate_id: who.clone(),
+				slashed_amount,
+			});
+
 			if left_amount < T::MinCandidateBond::get() {
 				return Self::deregister_candidate_inner(who);
 			}
 
 			candidate_detail.update_bond(left_amount);
 			CandidatePool::<T>::set(&who, Some(candidate_detail));
-
-			Self::release_candidate_bonds(&who, amount)?;
-
-			Self::deposit_event(Event::CandidateBondSlashed {
-				candidate_id: who,
-				slashed_amount: amount,
-			});
+			Self::update_candidate_bag(&who);
 
 			Ok(())
 		}
@@ -1604,4 +3866,59 @@ pub mod pallet {
 			T::FindAuthor::find_author::<'_, Vec<_>>(Default::default())
 		}
 	}
+
+	/// Rewards the block author noted by `pallet_authorship`, so that the author can be found from
+	/// the consensus digests (Aura, Babe) without a custom `FindAuthor`.
+	impl<T: Config> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+		fn note_author(author: T::AccountId) {
+			Self::reward_block_author_once(&author);
+		}
+	}
+
+	/// Every session of `pallet_session` is an epoch when the pallet is configured with
+	/// [`EpochTrigger::Session`]. The validator set is elected when the session is planned and
+	/// installed when the session starts. With [`EpochTrigger::BlockNumber`], the session pallet
+	/// keeps its own validator set.
+	impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+		fn new_session(_new_index: u32) -> Option<Vec<T::AccountId>> {
+			if T::EpochTrigger::get() != EpochTrigger::Session {
+				return None;
+			}
+			Self::plan_session_validator_set()
+		}
+
+		fn new_session_genesis(new_index: u32) -> Option<Vec<T::AccountId>> {
+			if T::EpochTrigger::get() != EpochTrigger::Session {
+				return None;
+			}
+			if new_index.is_zero() {
+				// The genesis validator set is elected again, as the session keys are only
+				// registered by the genesis config of `pallet_session`
+				let active_validator_set = Self::select_active_validator_set();
+				DeferredChallengers::<T>::kill();
+				if Self::is_election_failed(&active_validator_set) {
+					return None;
+				}
+				Self::set_active_validator_set(&active_validator_set);
+				Self::record_epoch_start(Zero::zero(), &active_validator_set);
+				return Some(
+					active_validator_set
+						.into_iter()
+						.map(|(active_validator, _, _)| active_validator)
+						.collect(),
+				);
+			}
+			Self::plan_session_validator_set()
+		}
+
+		fn end_session(_end_index: u32) {}
+
+		fn start_session(start_index: u32) {
+			// The genesis session uses the validator set installed by the genesis config
+			if T::EpochTrigger::get() != EpochTrigger::Session || start_index.is_zero() {
+				return;
+			}
+			Self::enact_session_validator_set();
+		}
+	}
 }
@@ -0,0 +1,320 @@
+//! Storage migrations of the pallet.
+
+use crate::{
+	types::{CandidateDetail, DelayActionRequest, EpochSnapshot, ValidatorStatus},
+	BalanceOf, CandidatePool, Config, DelayActionRequests, EpochDelegations, EpochIndex,
+	EpochSnapshots, NextEpochStartBlock, Pallet,
+};
+use frame::deps::{
+	frame_support::{
+		migrations::VersionedMigration, pallet_prelude::*, storage_alias,
+		traits::UncheckedOnRuntimeUpgrade,
+	},
+	frame_system::{self, pallet_prelude::BlockNumberFor},
+};
+use sp_runtime::{traits::Zero, Perbill, Saturating};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+
+pub mod v1 {
+	use super::*;
+
+	/// Seeds `NextEpochStartBlock` for the chains that moved to the next epoch on every block
+	/// that is a multiple of `EpochDuration`. The next epoch starts at the next such block, or at
+	/// the current block if it is one, so that the epoch schedule of the chain does not shift.
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let now = frame_system::Pallet::<T>::block_number();
+			let epoch_duration = Pallet::<T>::epoch_duration();
+			let next_epoch_start_block = if epoch_duration.is_zero() {
+				now
+			} else {
+				let elapsed = now % epoch_duration;
+				if elapsed.is_zero() {
+					now
+				} else {
+					now.saturating_add(epoch_duration.saturating_sub(elapsed))
+				}
+			};
+			NextEpochStartBlock::<T>::put(next_epoch_start_block);
+			T::DbWeight::get().reads_writes(2, 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let next_epoch_start_block = NextEpochStartBlock::<T>::get();
+			ensure!(next_epoch_start_block >= now, "The next epoch starts in the past");
+			ensure!(
+				next_epoch_start_block < now.saturating_add(Pallet::<T>::epoch_duration()) ||
+					Pallet::<T>::epoch_duration().is_zero(),
+				"The next epoch starts later than one epoch from now"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV0ToV1`] wrapped in a [`VersionedMigration`], it only runs when the on-chain
+	/// storage version of the pallet is 0 and bumps it to 1
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The candidate detail before the candidates set their own commission
+	#[derive(Encode, Decode)]
+	pub struct OldCandidateDetail<T: Config> {
+		pub bond: BalanceOf<T>,
+		pub total_delegations: BalanceOf<T>,
+		pub status: ValidatorStatus,
+	}
+
+	/// Translates the candidates of `CandidatePool` stored before the candidates set their own
+	/// commission. The candidates get the commission of a new candidate, which they have never
+	/// updated.
+	pub struct InnerMigrateV1ToV2<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			CandidatePool::<T>::translate::<OldCandidateDetail<T>, _>(|_, old_detail| {
+				translated.saturating_inc();
+				Some(CandidateDetail {
+					bond: old_detail.bond,
+					total_delegations: old_detail.total_delegations,
+					status: old_detail.status,
+					commission: Perbill::zero(),
+					commission_updated_at: None,
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(CandidatePool::<T>::count().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..])
+				.map_err(|_| "The candidate count could not be decoded")?;
+			ensure!(
+				CandidatePool::<T>::iter_values().count() as u32 == count,
+				"Not every candidate could be translated"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV1ToV2`] wrapped in a [`VersionedMigration`], it only runs when the on-chain
+	/// storage version of the pallet is 1 and bumps it to 2
+	pub type MigrateV1ToV2<T> = VersionedMigration<
+		1,
+		2,
+		InnerMigrateV1ToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Puts the candidates registered before the sorted candidate index was introduced into the
+	/// bags matching their total stake, so that they are electable again. The candidates that
+	/// are in a bag already are left in place.
+	pub struct InnerMigrateV2ToV3<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut candidates = 0u64;
+			for candidate in CandidatePool::<T>::iter_keys() {
+				candidates.saturating_inc();
+				Pallet::<T>::update_candidate_bag(&candidate);
+			}
+			T::DbWeight::get().reads_writes(
+				candidates.saturating_mul(4).saturating_add(1),
+				candidates.saturating_mul(2),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			for (candidate, candidate_detail) in CandidatePool::<T>::iter() {
+				ensure!(
+					crate::CandidateBagOf::<T>::get(&candidate) ==
+						Some(Pallet::<T>::bag_threshold_of(candidate_detail.total())),
+					"The candidate is not in the bag of its total stake"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV2ToV3`] wrapped in a [`VersionedMigration`], it only runs when the on-chain
+	/// storage version of the pallet is 2 and bumps it to 3
+	pub type MigrateV2ToV3<T> = VersionedMigration<
+		2,
+		3,
+		InnerMigrateV2ToV3<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// The delay action request before the delays could be measured in wall-clock time
+	#[derive(Encode, Decode)]
+	pub struct OldDelayActionRequest<T: Config> {
+		pub created_at: BlockNumberFor<T>,
+		pub delay_for: BlockNumberFor<T>,
+		pub amount: Option<BalanceOf<T>>,
+		pub target: Option<T::AccountId>,
+	}
+
+	/// Translates the delay action requests stored before the delays could be measured in
+	/// wall-clock time. The requests have no creation timestamp, so their delays are still
+	/// measured in blocks.
+	pub struct InnerMigrateV3ToV4<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			DelayActionRequests::<T>::translate::<OldDelayActionRequest<T>, _>(
+				|_, _, old_request| {
+					translated.saturating_inc();
+					Some(DelayActionRequest {
+						created_at: old_request.created_at,
+						delay_for: old_request.delay_for,
+						created_at_time: None,
+						amount: old_request.amount,
+						target: old_request.target,
+					})
+				},
+			);
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((DelayActionRequests::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..])
+				.map_err(|_| "The request count could not be decoded")?;
+			ensure!(
+				DelayActionRequests::<T>::iter_values().count() as u32 == count,
+				"Not every delay action request could be translated"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV3ToV4`] wrapped in a [`VersionedMigration`], it only runs when the on-chain
+	/// storage version of the pallet is 3 and bumps it to 4
+	pub type MigrateV3ToV4<T> = VersionedMigration<
+		3,
+		4,
+		InnerMigrateV3ToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// The snapshot of the current epoch before the snapshots were stored per epoch index, with
+	/// the delegations mapped by `(delegator, validator)`
+	#[derive(Encode, Decode)]
+	pub struct OldEpochSnapshot<T: Config> {
+		pub validators: BTreeMap<T::AccountId, BalanceOf<T>>,
+		pub delegations: BTreeMap<(T::AccountId, T::AccountId), BalanceOf<T>>,
+	}
+
+	/// The snapshot of the current epoch before the snapshots were stored per epoch index
+	#[storage_alias]
+	pub type LastEpochSnapshot<T: Config> = StorageValue<Pallet<T>, OldEpochSnapshot<T>>;
+
+	/// Moves the snapshot of `LastEpochSnapshot` into `EpochSnapshots` under the current epoch
+	/// index and stores its delegations into `EpochDelegations`, then opens the reward pools of
+	/// its validators as at the start of an epoch, so that the blocks of the rest of the epoch are
+	/// rewarded. The old snapshot is removed. If the current epoch has a snapshot already, it is
+	/// kept.
+	pub struct InnerMigrateV4ToV5<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV4ToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let Some(old_snapshot) = LastEpochSnapshot::<T>::take() else {
+				return T::DbWeight::get().reads(1);
+			};
+			let epoch_index = EpochIndex::<T>::get();
+			if EpochSnapshots::<T>::contains_key(epoch_index) {
+				return T::DbWeight::get().reads_writes(3, 1);
+			}
+
+			let mut epoch_snapshot = EpochSnapshot::<T>::default();
+			for (validator, bond) in old_snapshot.validators.iter() {
+				epoch_snapshot.add_validator(validator.clone(), *bond);
+			}
+			let mut delegations = 0u64;
+			for ((delegator, validator), amount) in old_snapshot.delegations {
+				if !old_snapshot.validators.contains_key(&validator) {
+					continue;
+				}
+				delegations.saturating_inc();
+				EpochDelegations::<T>::insert((epoch_index, &delegator, &validator), amount);
+				epoch_snapshot.add_delegation(validator, amount);
+			}
+			let validators = old_snapshot.validators.len() as u64;
+			Pallet::<T>::store_epoch_snapshot(epoch_index, epoch_snapshot);
+
+			T::DbWeight::get().reads_writes(
+				validators.saturating_add(5),
+				validators.saturating_add(delegations).saturating_add(2),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok(LastEpochSnapshot::<T>::exists().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let had_snapshot = bool::decode(&mut &state[..])
+				.map_err(|_| "The snapshot flag could not be decoded")?;
+			ensure!(!LastEpochSnapshot::<T>::exists(), "The old snapshot is not removed");
+			ensure!(
+				!had_snapshot || EpochSnapshots::<T>::contains_key(EpochIndex::<T>::get()),
+				"The current epoch has no snapshot"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV4ToV5`] wrapped in a [`VersionedMigration`], it only runs when the on-chain
+	/// storage version of the pallet is 4 and bumps it to 5
+	pub type MigrateV4ToV5<T> = VersionedMigration<
+		4,
+		5,
+		InnerMigrateV4ToV5<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -1,14 +1,16 @@
 use crate::{
 	self as pallet_dpos,
 	constants::{AccountId, Balance, *},
-	types::CandidateSet,
-	BalanceOf, OnSlashHandler, ReportNewValidatorSet,
+	types::{CandidateSet, EpochTrigger, ExpiredRewardDestination, RewardModel, RewardSource},
+	BalanceOf, ElectionProvider, OnSlashHandler, ReportNewValidatorSet, SequentialPhragmenElection,
+	StakeWeightRotationElection, TopStakeElection,
 };
 use frame::{
 	deps::{
 		frame_support::{
 			derive_impl, parameter_types,
-			traits::{ConstU16, ConstU32, ConstU64, FindAuthor, Hooks},
+			traits::{ConstU16, ConstU32, ConstU64, FindAuthor, Hooks, ValidatorRegistration},
+			PalletId,
 		},
 		frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot},
 	},
@@ -17,7 +19,7 @@ use frame::{
 use lazy_static::lazy_static;
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
 	BuildStorage,
 };
 
@@ -28,6 +30,7 @@ frame::deps::frame_support::construct_runtime! {
 	pub struct Test {
 		System: frame::deps::frame_system,
 		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
 		Dpos: pallet_dpos,
 	}
 }
@@ -38,14 +41,33 @@ parameter_types! {
 	pub static ExistentialDeposit : u128 = 1;
 	pub static MaxActiveValidators: u32 = 10;
 	pub static MinActiveValidators: u32 = 1;
+	pub static MaxElectableCandidates: u32 = 100;
+	pub static MaxValidatorChurn: u32 = 100;
+	pub static ElectionLookahead: u64 = 0;
+	pub static SnapshotPageSize: u32 = 2;
+	pub const BagThresholds: &'static [Balance] = &[10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000];
+	pub static DposElectionMode: TestElectionMode = TestElectionMode::TopStake;
+	pub static RotationSlots: u32 = 1;
+	pub static LastElectionEdges: u32 = 0;
 	pub static MaxDelegateCount : u32 = 20;
 	pub static DelayDeregisterCandidateDuration : u64 = TEST_BLOCKS_PER_EPOCH;
 	pub static DelayUndelegateCandidate : u64 = TEST_BLOCKS_PER_EPOCH;
 	pub static EpochDuration : u64 = TEST_BLOCKS_PER_EPOCH;
+	pub static DposEpochTrigger: EpochTrigger = EpochTrigger::BlockNumber;
+	pub static ExpectedBlockTime: u64 = TEST_BLOCK_TIME;
+	pub static HistoryDepth: u32 = 10;
+	pub static MaxPayoutBatch: u32 = 10;
+	pub static RewardExpiryEpochs: u32 = 0;
+	pub static DposExpiredRewardDestination: ExpiredRewardDestination<AccountId> =
+		ExpiredRewardDestination::Burn;
+	pub static AccountsWithoutSessionKeys: Vec<AccountId> = vec![];
+	pub static FindAuthorDisabled: bool = false;
 	pub static MinCandidateBond : u128 = 10;
 	pub static MinDelegateAmount : u128 = 10;
-	pub static ValidatorCommission : u32 = 3; // 0.3
-	pub static DelegatorCommission : u32 = 1; // 0.1
+	pub static DposRewardModel: RewardModel = RewardModel::BlockRewards;
+	pub static EpochReward: u128 = 0;
+	pub static DposRewardSource: RewardSource<AccountId> = RewardSource::Mint;
+	pub const DposPalletId: PalletId = PalletId(*b"py/dpos_");
 }
 
 pub const REGISTRATION_HOLD_AMOUNT: u128 = 200;
@@ -122,13 +144,16 @@ impl FindAuthor<AccountId> for RoundRobinAuthor {
 	where
 		I: 'a + IntoIterator<Item = ([u8; 4], &'a [u8])>,
 	{
+		if FindAuthorDisabled::get() {
+			return None;
+		}
 		let current_active_validators = Dpos::active_validators();
 		let active_validator_ids = current_active_validators
 			.iter()
 			.map(|(id, _, _)| *id)
 			.collect::<Vec<AccountId>>();
 
-		if active_validator_ids.len() == 0 {
+		if active_validator_ids.is_empty() {
 			return None;
 		}
 		active_validator_ids
@@ -145,6 +170,67 @@ impl OnSlashHandler<AccountId, Balance> for DoNothing {
 	fn on_slash(_who: &AccountId, _balance: Balance) {}
 }
 
+/// Every account has registered its session keys except `AccountsWithoutSessionKeys`
+pub struct TestSessionKeys;
+impl ValidatorRegistration<AccountId> for TestSessionKeys {
+	fn is_registered(id: &AccountId) -> bool {
+		!AccountsWithoutSessionKeys::get().contains(id)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestElectionMode {
+	TopStake,
+	SequentialPhragmen,
+	StakeWeightRotation,
+	/// Elects the candidates with the lowest total stake, used to verify that the election can be
+	/// replaced by a custom provider
+	LowestStake,
+}
+
+pub struct TestElection;
+impl ElectionProvider<AccountId, Balance> for TestElection {
+	fn elect(
+		to_elect: u32,
+		mut candidates: Vec<(AccountId, Balance, Balance)>,
+		edges: Vec<(AccountId, AccountId, Balance)>,
+	) -> Vec<(AccountId, Balance, Balance)> {
+		LastElectionEdges::set(edges.len() as u32);
+		match DposElectionMode::get() {
+			TestElectionMode::TopStake => TopStakeElection::elect(to_elect, candidates, edges),
+			TestElectionMode::SequentialPhragmen =>
+				SequentialPhragmenElection::elect(to_elect, candidates, edges),
+			TestElectionMode::StakeWeightRotation => StakeWeightRotationElection::<
+				Test,
+				RotationSlots,
+			>::elect(to_elect, candidates, edges),
+			TestElectionMode::LowestStake => {
+				candidates.sort_by_key(|&(_, _, total)| total);
+				candidates.into_iter().take(to_elect as usize).collect()
+			},
+		}
+	}
+
+	fn needs_edges() -> bool {
+		match DposElectionMode::get() {
+			TestElectionMode::TopStake =>
+				<TopStakeElection as ElectionProvider<AccountId, Balance>>::needs_edges(),
+			TestElectionMode::SequentialPhragmen =>
+				<SequentialPhragmenElection as ElectionProvider<AccountId, Balance>>::needs_edges(),
+			TestElectionMode::StakeWeightRotation =>
+				StakeWeightRotationElection::<Test, RotationSlots>::needs_edges(),
+			TestElectionMode::LowestStake => false,
+		}
+	}
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
 impl pallet_dpos::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type NativeBalance = Balances;
@@ -154,15 +240,33 @@ impl pallet_dpos::Config for Test {
 	type WeightInfo = ();
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type MaxActiveValidators = MaxActiveValidators;
+	type BagThresholds = BagThresholds;
+	type MaxElectableCandidates = MaxElectableCandidates;
+	type MaxValidatorChurn = MaxValidatorChurn;
 	type MinActiveValidators = MinActiveValidators;
+	type ElectionLookahead = ElectionLookahead;
+	type SnapshotPageSize = SnapshotPageSize;
+	type ElectionProvider = TestElection;
 	type MaxDelegateCount = MaxDelegateCount;
 	type DelayDeregisterCandidateDuration = DelayDeregisterCandidateDuration;
 	type DelayUndelegateCandidate = DelayUndelegateCandidate;
 	type EpochDuration = EpochDuration;
+	type EpochTrigger = DposEpochTrigger;
+	type Time = Timestamp;
+	type ExpectedBlockTime = ExpectedBlockTime;
+	type HistoryDepth = HistoryDepth;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ValidatorRegistration = TestSessionKeys;
 	type MinCandidateBond = MinCandidateBond;
 	type MinDelegateAmount = MinDelegateAmount;
-	type AuthorCommission = ValidatorCommission;
-	type DelegatorCommission = DelegatorCommission;
+	type RewardModel = DposRewardModel;
+	type EpochReward = EpochReward;
+	type RewardSource = DposRewardSource;
+	type PalletId = DposPalletId;
+	type MaxPayoutBatch = MaxPayoutBatch;
+	type RewardExpiryEpochs = RewardExpiryEpochs;
+	type ExpiredRewardDestination = DposExpiredRewardDestination;
 	type FindAuthor = RoundRobinAuthor;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type ConfigControllerOrigin = EnsureRoot<AccountId>;
@@ -180,7 +284,7 @@ impl Default for TestExtBuilder {
 		Self {
 			gensis_candidates: DEFAULT_ACTIVE_SET.to_vec(),
 			reward_distribution_disabled: false,
-			balance_rate: 1000,
+			balance_rate: 30,
 		}
 	}
 }
@@ -193,6 +297,26 @@ impl TestExtBuilder {
 		self
 	}
 
+	pub fn epoch_trigger(&mut self, epoch_trigger: EpochTrigger) -> &mut Self {
+		DposEpochTrigger::set(epoch_trigger);
+		self
+	}
+
+	pub fn expected_block_time(&mut self, expected_block_time: u64) -> &mut Self {
+		ExpectedBlockTime::set(expected_block_time);
+		self
+	}
+
+	pub fn history_depth(&mut self, history_depth: u32) -> &mut Self {
+		HistoryDepth::set(history_depth);
+		self
+	}
+
+	pub fn accounts_without_session_keys(&mut self, accounts: Vec<AccountId>) -> &mut Self {
+		AccountsWithoutSessionKeys::set(accounts);
+		self
+	}
+
 	pub fn min_candidate_bond(&mut self, min_candidate_bond: BalanceOf<Test>) -> &mut Self {
 		MinCandidateBond::set(min_candidate_bond);
 		self
@@ -203,6 +327,16 @@ impl TestExtBuilder {
 		self
 	}
 
+	pub fn election_mode(&mut self, election_mode: TestElectionMode) -> &mut Self {
+		DposElectionMode::set(election_mode);
+		self
+	}
+
+	pub fn rotation_slots(&mut self, rotation_slots: u32) -> &mut Self {
+		RotationSlots::set(rotation_slots);
+		self
+	}
+
 	pub fn genesis_candidates(&mut self, candidates: CandidateSet<Test>) -> &mut Self {
 		self.gensis_candidates = candidates;
 		self
@@ -218,6 +352,26 @@ impl TestExtBuilder {
 		self
 	}
 
+	pub fn max_electable_candidates(&mut self, max_electable_candidates: u32) -> &mut Self {
+		MaxElectableCandidates::set(max_electable_candidates);
+		self
+	}
+
+	pub fn election_lookahead(&mut self, election_lookahead: u64) -> &mut Self {
+		ElectionLookahead::set(election_lookahead);
+		self
+	}
+
+	pub fn snapshot_page_size(&mut self, snapshot_page_size: u32) -> &mut Self {
+		SnapshotPageSize::set(snapshot_page_size);
+		self
+	}
+
+	pub fn max_validator_churn(&mut self, max_validator_churn: u32) -> &mut Self {
+		MaxValidatorChurn::set(max_validator_churn);
+		self
+	}
+
 	pub fn min_active_validators(&mut self, min_active_validators: u32) -> &mut Self {
 		MinActiveValidators::set(min_active_validators);
 		self
@@ -238,18 +392,34 @@ impl TestExtBuilder {
 		self
 	}
 
-	pub fn validator_commission(&mut self, validator_commission: u32) -> &mut Self {
-		ValidatorCommission::set(validator_commission);
+	pub fn reward_distribution_disabled(&mut self) -> &mut Self {
+		self.reward_distribution_disabled = true;
 		self
 	}
 
-	pub fn reward_distribution_disabled(&mut self) -> &mut Self {
-		self.reward_distribution_disabled = true;
+	pub fn find_author_disabled(&mut self) -> &mut Self {
+		FindAuthorDisabled::set(true);
+		self
+	}
+
+	pub fn reward_source(&mut self, reward_source: RewardSource<AccountId>) -> &mut Self {
+		DposRewardSource::set(reward_source);
+		self
+	}
+
+	pub fn reward_expiry(
+		&mut self,
+		expiry_epochs: u32,
+		destination: ExpiredRewardDestination<AccountId>,
+	) -> &mut Self {
+		RewardExpiryEpochs::set(expiry_epochs);
+		DposExpiredRewardDestination::set(destination);
 		self
 	}
 
-	pub fn delegator_commission(&mut self, delegator_commission: u32) -> &mut Self {
-		DelegatorCommission::set(delegator_commission);
+	pub fn epoch_points_rewards(&mut self, epoch_reward: BalanceOf<Test>) -> &mut Self {
+		DposRewardModel::set(RewardModel::EpochPoints);
+		EpochReward::set(epoch_reward);
 		self
 	}
 
@@ -267,8 +437,7 @@ impl TestExtBuilder {
 	}
 
 	fn with_storage(&self) -> sp_io::TestExternalities {
-		let mut storage =
-			frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 
 		let _ = pallet_balances::GenesisConfig::<Test> {
 			balances: vec![
@@ -339,11 +508,9 @@ impl TestExtBuilder {
 
 	pub fn run_to_block(&self, n: BlockNumberFor<Test>) {
 		while System::block_number() < n {
-			if System::block_number() > 1 {
-				if !self.reward_distribution_disabled {
-					Dpos::on_finalize(System::block_number());
-					System::on_finalize(System::block_number());
-				}
+			if System::block_number() > 1 && !self.reward_distribution_disabled {
+				Dpos::on_finalize(System::block_number());
+				System::on_finalize(System::block_number());
 			}
 			self.next_block();
 		}
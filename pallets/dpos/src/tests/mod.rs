@@ -5,6 +5,8 @@ pub fn ros(indx: u64) -> RuntimeOrigin {
 	RuntimeOrigin::signed(indx)
 }
 
+#[cfg(test)]
+mod test_candidate_bags;
 #[cfg(test)]
 mod test_candidate_bond_less;
 #[cfg(test)]
@@ -18,11 +20,53 @@ mod test_delay_undelegate_candidate;
 #[cfg(test)]
 mod test_delegate_candidate;
 #[cfg(test)]
+mod test_epoch_history;
+#[cfg(test)]
 mod test_force_deregister_candidate;
 #[cfg(test)]
+mod test_force_epoch;
+#[cfg(test)]
+mod test_force_set_emergency_validators;
+#[cfg(test)]
 mod test_force_undelegate_candidate;
 mod test_helpers;
 #[cfg(test)]
+mod test_inflation_curve;
+#[cfg(test)]
+mod test_migrations;
+#[cfg(test)]
+mod test_note_author;
+#[cfg(test)]
+mod test_paged_election;
+#[cfg(test)]
+mod test_payout_epoch;
+#[cfg(test)]
+mod test_payout_rewards;
+#[cfg(test)]
+mod test_phragmen_election;
+#[cfg(test)]
+mod test_preview_validator_set;
+#[cfg(test)]
 mod test_register_as_candidate;
 #[cfg(test)]
+mod test_reward_destination;
+#[cfg(test)]
+mod test_reward_expiry;
+#[cfg(test)]
+mod test_reward_pools;
+#[cfg(test)]
+mod test_reward_source;
+#[cfg(test)]
+mod test_session_manager;
+#[cfg(test)]
+mod test_set_commission;
+#[cfg(test)]
+mod test_set_epoch_duration;
+#[cfg(test)]
+mod test_timestamp_epochs;
+#[cfg(test)]
+mod test_validator_churn;
+#[cfg(test)]
 mod test_validator_election;
+#[cfg(test)]
+mod test_validator_rotation;
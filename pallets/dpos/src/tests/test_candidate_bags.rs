@@ -0,0 +1,142 @@
+use crate::{mock::*, *};
+use constants::*;
+use frame::deps::frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+
+use tests::ros;
+
+#[test]
+fn should_ok_candidate_bag_follows_total_stake() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 100)]).build().execute_with(|| {
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(100));
+		assert_eq!(CandidateBags::<Test>::get(100).to_vec(), vec![CANDIDATE_1.id]);
+
+		// Total stake 150
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 50));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(200));
+		assert!(!CandidateBags::<Test>::contains_key(100));
+		Dpos::do_try_state();
+
+		// Total stake 550
+		assert_ok!(Dpos::candidate_bond_more(ros(CANDIDATE_1.id), 400));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(1_000));
+		Dpos::do_try_state();
+
+		// Total stake 250
+		assert_ok!(Dpos::candidate_bond_less(ros(CANDIDATE_1.id), 300));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(500));
+		Dpos::do_try_state();
+
+		// Total stake 200
+		assert_ok!(Dpos::force_undelegate_candidate(
+			RuntimeOrigin::root(),
+			ACCOUNT_6.id,
+			CANDIDATE_1.id,
+			50
+		));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(200));
+		Dpos::do_try_state();
+
+		assert_ok!(Dpos::force_deregister_candidate(RuntimeOrigin::root(), CANDIDATE_1.id));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), None);
+		assert_eq!(CandidateBags::<Test>::iter().count(), 0);
+		Dpos::do_try_state();
+	});
+}
+
+#[test]
+fn should_ok_candidate_above_highest_threshold_put_into_last_bag() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 6_000)]).build().execute_with(|| {
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(Balance::MAX));
+		assert_eq!(Dpos::bag_thresholds().last(), Some(&Balance::MAX));
+		Dpos::do_try_state();
+	});
+}
+
+#[test]
+fn should_ok_electable_candidates_read_from_top_bags() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 120),
+		(CANDIDATE_2.id, 150),
+		(CANDIDATE_3.id, 600),
+		(CANDIDATE_4.id, 30),
+		(CANDIDATE_5.id, 180),
+	])
+	.max_active_validators(2)
+	.max_electable_candidates(2)
+	.build()
+	.execute_with(|| {
+		// Candidate 3 is alone in the top bag, the next bag is read entirely before the election
+		// set is cut off
+		assert_eq!(
+			Dpos::get_electable_candidate_set(),
+			vec![(CANDIDATE_3.id, 600, 600), (CANDIDATE_5.id, 180, 180)]
+		);
+
+		// Offline candidates are skipped
+		assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_3.id)));
+		assert_eq!(
+			Dpos::get_electable_candidate_set(),
+			vec![(CANDIDATE_5.id, 180, 180), (CANDIDATE_2.id, 150, 150)]
+		);
+		assert_eq!(
+			Dpos::select_active_validator_set(),
+			vec![(CANDIDATE_5.id, 180, 180), (CANDIDATE_2.id, 150, 150)]
+		);
+		Dpos::do_try_state();
+	});
+}
+
+#[test]
+fn should_ok_slashed_candidate_moves_down_the_bags() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 600), (CANDIDATE_2.id, 400)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(1_000));
+
+			// Bond 600 -> 300
+			assert_ok!(Dpos::do_slash(CANDIDATE_1.id, 300));
+			assert_eq!(Dpos::get_candidate(&CANDIDATE_1.id).unwrap().bond, 300);
+			assert_eq!(
+				Balances::balance_on_hold(
+					&HoldReason::CandidateBondReserved.into(),
+					&CANDIDATE_1.id
+				),
+				300
+			);
+			assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), Some(500));
+			assert_eq!(
+				CandidateBags::<Test>::get(500).to_vec(),
+				vec![CANDIDATE_2.id, CANDIDATE_1.id]
+			);
+			assert!(!CandidateBags::<Test>::contains_key(1_000));
+			System::assert_last_event(RuntimeEvent::Dpos(Event::CandidateBondSlashed {
+				candidate_id: CANDIDATE_1.id,
+				slashed_amount: 300,
+			}));
+			Dpos::do_try_state();
+		});
+}
+
+#[test]
+fn should_ok_slashed_candidate_under_minimum_bond_removed_from_bags() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 100)]).build().execute_with(|| {
+		assert_ok!(Dpos::do_slash(CANDIDATE_1.id, 95));
+		assert!(!Dpos::is_candidate(&CANDIDATE_1.id));
+		assert_eq!(CandidateBagOf::<Test>::get(CANDIDATE_1.id), None);
+		assert_eq!(CandidateBags::<Test>::iter().count(), 0);
+		Dpos::do_try_state();
+	});
+}
+
+#[test]
+fn should_failed_slash_non_candidate() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 100)]).build().execute_with(|| {
+		assert_noop!(Dpos::do_slash(ACCOUNT_6.id, 10), Error::<Test>::CandidateDoesNotExist);
+	});
+}
@@ -30,28 +30,16 @@ fn should_ok_should_claim_rewards() {
 			ext.run_to_block(4);
 
 			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300));
 			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), 0);
 
 			ext.next_block(); // block 5
 			ext.next_block(); // block 6 - new epoch
 
 			assert_eq!(Dpos::active_validators().len(), 3);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200));
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300));
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400));
 			test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 500);
 			test_helpers::register_new_candidate(CANDIDATE_5.id, CANDIDATE_5.balance, 600);
 			test_helpers::register_new_candidate(CANDIDATE_6.id, CANDIDATE_6.balance, 700);
@@ -59,18 +47,9 @@ fn should_ok_should_claim_rewards() {
 			ext.next_block(); // block 7
 			ext.next_block(); // block 8
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400));
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
@@ -79,18 +58,9 @@ fn should_ok_should_claim_rewards() {
 			assert_eq!(Dpos::active_validators().len(), 3);
 
 			// Old validator set will stop producing blocks and receive reward from this epoch
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
 			// New validator set will replace because they have top delegations
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), 0);
@@ -99,102 +69,39 @@ fn should_ok_should_claim_rewards() {
 			ext.next_block(); // block 10
 
 			assert_eq!(Dpos::active_validators().len(), 3);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
 
 			ext.next_block(); // block 11
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
 
 			ext.next_block(); // block 12
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			); // Author
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_6.id),
-				Dpos::calculate_reward(700, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2); // Author
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
+			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), Dpos::block_reward(700));
 
 			ext.next_block(); // block 13
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission()) * 2
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_6.id),
-				Dpos::calculate_reward(700, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), Dpos::block_reward(700));
 
 			// Multiple accounts claim the reward
 			let (candidate_balance_1, reward_points_1) =
@@ -37,7 +37,9 @@ fn should_ok_delay_deregister_sucessfully() {
 			Some(CandidateDetail {
 				bond: hold_amount,
 				total_delegations: 0,
-				status: types::ValidatorStatus::Offline
+				status: types::ValidatorStatus::Offline,
+				commission: Default::default(),
+				commission_updated_at: None,
 			})
 		);
 		assert_eq!(
@@ -46,7 +48,8 @@ fn should_ok_delay_deregister_sucessfully() {
 				amount: None,
 				created_at: 10,
 				delay_for: <mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-				target: None
+				target: None,
+				created_at_time: None
 			})
 		);
 
@@ -92,7 +95,8 @@ fn should_ok_delay_deregister_all_candidates_sucessfully() {
 						created_at: 1010,
 						delay_for:
 							<mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-						target: None
+						target: None,
+						created_at_time: None
 					})
 				);
 
@@ -177,7 +181,9 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 					Some(CandidateDetail {
 						bond,
 						total_delegations: 0,
-						status: types::ValidatorStatus::Online
+						status: types::ValidatorStatus::Online,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_ok!(Dpos::delay_deregister_candidate(ros(candidate)));
@@ -189,7 +195,8 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 						created_at: 1010,
 						delay_for:
 							<mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-						target: None
+						target: None,
+						created_at_time: None
 					})
 				);
 				assert_ok!(Dpos::delegate_candidate(
@@ -202,7 +209,9 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 					Some(CandidateDetail {
 						bond,
 						total_delegations: delegated_amount,
-						status: types::ValidatorStatus::Offline
+						status: types::ValidatorStatus::Offline,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_eq!(DelegateCountMap::<Test>::get(ACCOUNT_6.id), (indx + 1) as u32);
@@ -222,13 +231,15 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 
 			ext.run_to_block_from(1010, HALF_EPOCH);
 
-			for (_, (candidate, bond)) in DEFAULT_ACTIVE_SET.clone().into_iter().enumerate() {
+			for (candidate, bond) in DEFAULT_ACTIVE_SET.clone().into_iter() {
 				assert_eq!(
 					CandidatePool::<Test>::get(candidate),
 					Some(CandidateDetail {
 						bond,
 						total_delegations: delegated_amount,
-						status: types::ValidatorStatus::Offline
+						status: types::ValidatorStatus::Offline,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_noop!(
@@ -240,7 +251,9 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 					Some(CandidateDetail {
 						bond,
 						total_delegations: delegated_amount,
-						status: types::ValidatorStatus::Offline
+						status: types::ValidatorStatus::Offline,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 
@@ -252,7 +265,8 @@ fn should_failed_delay_deregister_candidates_before_due_date() {
 						created_at: 1010,
 						delay_for:
 							<mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-						target: None
+						target: None,
+						created_at_time: None
 					})
 				);
 				assert_eq!(
@@ -299,7 +313,9 @@ fn should_ok_cancel_deregister_candidate_requests() {
 					Some(CandidateDetail {
 						bond: hold_amount,
 						total_delegations: 0,
-						status: types::ValidatorStatus::Online
+						status: types::ValidatorStatus::Online,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_ok!(Dpos::delay_deregister_candidate(ros(candidate)));
@@ -310,7 +326,8 @@ fn should_ok_cancel_deregister_candidate_requests() {
 						created_at: 1010,
 						delay_for:
 							<mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-						target: None
+						target: None,
+						created_at_time: None
 					})
 				);
 
@@ -324,7 +341,9 @@ fn should_ok_cancel_deregister_candidate_requests() {
 					Some(CandidateDetail {
 						bond: hold_amount,
 						total_delegations: delegated_amount,
-						status: types::ValidatorStatus::Offline
+						status: types::ValidatorStatus::Offline,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_eq!(DelegateCountMap::<Test>::get(ACCOUNT_6.id), (indx + 1) as u32);
@@ -344,14 +363,16 @@ fn should_ok_cancel_deregister_candidate_requests() {
 
 			ext.run_to_block_from(1010, HALF_EPOCH);
 
-			for (_, (candidate, bond)) in DEFAULT_ACTIVE_SET.clone().into_iter().enumerate() {
+			for (candidate, bond) in DEFAULT_ACTIVE_SET.clone().into_iter() {
 				assert_ok!(Dpos::cancel_deregister_candidate_request(ros(candidate)));
 				assert_eq!(
 					CandidatePool::<Test>::get(candidate),
 					Some(CandidateDetail {
 						bond,
 						total_delegations: delegated_amount,
-						status: types::ValidatorStatus::Online
+						status: types::ValidatorStatus::Online,
+						commission: Default::default(),
+						commission_updated_at: None,
 					})
 				);
 				assert_eq!(
@@ -389,7 +410,9 @@ fn should_failed_cancel_not_found_delay_action_request() {
 			Some(CandidateDetail {
 				bond: hold_amount,
 				total_delegations: 0,
-				status: types::ValidatorStatus::Offline
+				status: types::ValidatorStatus::Offline,
+				commission: Default::default(),
+				commission_updated_at: None,
 			})
 		);
 		assert_eq!(
@@ -398,7 +421,8 @@ fn should_failed_cancel_not_found_delay_action_request() {
 				amount: None,
 				created_at: 10,
 				delay_for: <mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-				target: None
+				target: None,
+				created_at_time: None
 			})
 		);
 
@@ -437,7 +461,9 @@ fn should_failed_deregister_while_in_delay_duration() {
 			Some(CandidateDetail {
 				bond: hold_amount,
 				total_delegations: 0,
-				status: types::ValidatorStatus::Offline
+				status: types::ValidatorStatus::Offline,
+				commission: Default::default(),
+				commission_updated_at: None,
 			})
 		);
 		assert_eq!(
@@ -446,7 +472,8 @@ fn should_failed_deregister_while_in_delay_duration() {
 				amount: None,
 				created_at: 10,
 				delay_for: <mock::Test as pallet::Config>::DelayDeregisterCandidateDuration::get(),
-				target: None
+				target: None,
+				created_at_time: None
 			})
 		);
 
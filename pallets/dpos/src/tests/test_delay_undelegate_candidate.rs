@@ -54,7 +54,8 @@ fn should_failed_undelegate_below_delegated_amount() {
 				amount: Some(delegated_amount - 1),
 				created_at: 1010,
 				delay_for: <mock::Test as pallet::Config>::DelayUndelegateCandidate::get(),
-				target: Some(candidate)
+				target: Some(candidate),
+				created_at_time: None
 			})
 		);
 
@@ -85,7 +86,9 @@ fn should_failed_undelegate_over_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(CandidatePool::<Test>::count(), 1);
@@ -105,7 +108,8 @@ fn should_failed_undelegate_over_amount() {
 					amount: Some(300),
 					created_at: 5,
 					delay_for: <mock::Test as pallet::Config>::DelayUndelegateCandidate::get(),
-					target: Some(candidate.id)
+					target: Some(candidate.id),
+					created_at_time: None
 				})
 			);
 
@@ -136,7 +140,9 @@ fn should_ok_undelegate_all_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -155,7 +161,8 @@ fn should_ok_undelegate_all_amount() {
 					amount: Some(200),
 					created_at: 5,
 					delay_for: <mock::Test as pallet::Config>::DelayUndelegateCandidate::get(),
-					target: Some(candidate.id)
+					target: Some(candidate.id),
+					created_at_time: None
 				})
 			);
 
@@ -171,7 +178,9 @@ fn should_ok_undelegate_all_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -195,7 +204,9 @@ fn should_ok_undelegate_partial_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(CandidatePool::<Test>::count(), 1);
@@ -217,7 +228,8 @@ fn should_ok_undelegate_partial_amount() {
 					amount: Some(75),
 					created_at: 10,
 					delay_for: <mock::Test as pallet::Config>::DelayUndelegateCandidate::get(),
-					target: Some(candidate.id)
+					target: Some(candidate.id),
+					created_at_time: None
 				})
 			);
 
@@ -236,7 +248,9 @@ fn should_ok_undelegate_partial_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 125,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -281,7 +295,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_4.id), ACCOUNT_4.balance - 200 + 75);
@@ -313,7 +329,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75 - 199,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance - 300 + 199);
@@ -339,7 +357,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75 - 199 - 101,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance - 300 + 199 + 101);
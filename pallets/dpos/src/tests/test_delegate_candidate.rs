@@ -99,7 +99,9 @@ fn should_ok_delegate_candidate_successfully() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 200,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -177,7 +179,9 @@ fn should_ok_one_delegator_one_candidate_successfully() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: sum_delegated_amount,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -261,7 +265,9 @@ fn should_ok_one_delegator_multiple_candidates_successfully() {
 				Some(CandidateDetail {
 					bond: 70,
 					total_delegations: delegated_amount_2,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -304,7 +310,9 @@ fn should_ok_one_delegator_multiple_candidates_successfully() {
 				Some(CandidateDetail {
 					bond: 70,
 					total_delegations: delegated_amount_3,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 		});
@@ -408,7 +416,8 @@ fn should_ok_multiple_delegators_one_candidate_successfully() {
 				delegated_by: delegator_3.id,
 				amount: delegated_amount_3,
 				total_delegated_amount: delegated_amount_1 +
-					delegated_amount_2 + delegated_amount_3,
+					delegated_amount_2 +
+					delegated_amount_3,
 			}));
 
 			assert_eq!(CandidateDelegators::<Test>::get(candidate.id).len(), 3);
@@ -440,7 +449,8 @@ fn should_ok_multiple_delegators_one_candidate_successfully() {
 				delegated_by: delegator_1.id,
 				amount: delegated_amount_1,
 				total_delegated_amount: delegated_amount_1 +
-					delegated_amount_2 + delegated_amount_3 +
+					delegated_amount_2 +
+					delegated_amount_3 +
 					delegated_amount_1,
 			}));
 
@@ -451,7 +461,9 @@ fn should_ok_multiple_delegators_one_candidate_successfully() {
 					total_delegations: delegated_amount_3 +
 						delegated_amount_1 + delegated_amount_2 +
 						delegated_amount_1,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
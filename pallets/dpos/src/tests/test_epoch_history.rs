@@ -0,0 +1,155 @@
+use crate::{mock::*, *};
+use constants::{Balance, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::frame_support::assert_ok;
+use tests::ros;
+
+fn total_reward_points() -> Balance {
+	[CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id, ACCOUNT_6.id]
+		.into_iter()
+		.map(Dpos::reward_points)
+		.sum()
+}
+
+#[test]
+fn should_ok_record_genesis_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		let epoch_info = Dpos::epoch_info(0).unwrap();
+		assert_eq!(epoch_info.start_block, 0);
+		assert_eq!(epoch_info.planned_end_block, TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			epoch_info.validators.to_vec(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+		);
+		assert_eq!(epoch_info.total_active_stake, 600);
+		assert_eq!(Dpos::current_epoch_info(), Some(epoch_info));
+	});
+}
+
+#[test]
+fn should_ok_record_epoch_rewards_and_next_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+
+		// The rewards of the blocks produced in the epoch are recorded
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let total_rewards = Dpos::epoch_info(0).unwrap().total_rewards;
+		assert!(total_rewards > 0);
+		assert_eq!(total_rewards, total_reward_points());
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		let total_rewards = total_reward_points();
+		assert_eq!(Dpos::epoch_info(0).unwrap().total_rewards, total_rewards);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::EpochEnded {
+			epoch: 0,
+			at_block: TEST_BLOCKS_PER_EPOCH,
+			total_rewards,
+		}));
+		System::assert_has_event(RuntimeEvent::Dpos(Event::EpochStarted {
+			epoch: 1,
+			start_block: TEST_BLOCKS_PER_EPOCH,
+			planned_end_block: TEST_BLOCKS_PER_EPOCH * 2,
+			total_validators: 3,
+			total_active_stake: 850,
+		}));
+
+		let epoch_info = Dpos::current_epoch_info().unwrap();
+		assert_eq!(
+			epoch_info.validators.to_vec(),
+			vec![CANDIDATE_3.id, CANDIDATE_1.id, CANDIDATE_2.id]
+		);
+		assert_eq!(epoch_info.total_active_stake, 850);
+		assert_eq!(epoch_info.total_rewards, 0);
+	});
+}
+
+#[test]
+fn should_ok_prune_epochs_older_than_history_depth() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(2)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 4);
+		assert_eq!(EpochIndex::<Test>::get(), 4);
+
+		assert_eq!(Dpos::epoch_info(0), None);
+		assert_eq!(Dpos::epoch_info(1), None);
+		assert!(Dpos::epoch_info(2).is_some());
+		assert!(Dpos::epoch_info(3).is_some());
+		assert!(Dpos::epoch_info(4).is_some());
+		assert_eq!(EpochHistory::<Test>::iter().count(), 3);
+	});
+}
+
+#[test]
+fn should_ok_keep_snapshot_of_each_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+
+		// The snapshot of the past epoch stays as it was captured
+		let genesis_snapshot = Dpos::epoch_snapshot(0).unwrap();
+		assert_eq!(genesis_snapshot.validators.len(), 3);
+		assert!(genesis_snapshot.total_delegations.is_empty());
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((0,)).count(), 0);
+
+		let epoch_snapshot = Dpos::epoch_snapshot(1).unwrap();
+		assert_eq!(epoch_snapshot.total_delegations_of(&CANDIDATE_3.id), 250);
+		assert_eq!(EpochDelegations::<Test>::get((1, ACCOUNT_6.id, CANDIDATE_3.id)), Some(250));
+		assert_eq!(Dpos::last_epoch_snapshot(), Some(epoch_snapshot));
+	});
+}
+
+#[test]
+fn should_ok_prune_snapshots_older_than_history_depth() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(2)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 4);
+		assert_eq!(EpochIndex::<Test>::get(), 4);
+
+		assert_eq!(Dpos::epoch_snapshot(0), None);
+		assert_eq!(Dpos::epoch_snapshot(1), None);
+		assert!(Dpos::epoch_snapshot(2).is_some());
+		assert!(Dpos::epoch_snapshot(3).is_some());
+		assert!(Dpos::epoch_snapshot(4).is_some());
+		assert_eq!(EpochSnapshots::<Test>::iter().count(), 3);
+	});
+}
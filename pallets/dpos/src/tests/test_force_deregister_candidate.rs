@@ -118,7 +118,9 @@ fn should_ok_deregister_with_delegations_sucessfully() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -0,0 +1,140 @@
+use crate::{mock::*, *};
+use constants::{
+	ACCOUNT_1, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use tests::{ros, test_helpers};
+use types::Forcing;
+
+#[test]
+fn should_failed_invalid_origin() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(Dpos::force_new_epoch(ros(ACCOUNT_1.id)), DispatchError::BadOrigin);
+		assert_noop!(Dpos::force_no_epochs(ros(ACCOUNT_1.id)), DispatchError::BadOrigin);
+		assert_noop!(Dpos::force_new_epoch_always(ros(ACCOUNT_1.id)), DispatchError::BadOrigin);
+		assert_eq!(Dpos::force_epoch(), Forcing::NotForcing);
+	});
+}
+
+#[test]
+fn should_ok_force_new_epoch_at_next_block() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(3);
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 500));
+		assert_ok!(Dpos::force_new_epoch(RuntimeOrigin::root()));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::EpochForcingSet {
+			mode: Forcing::ForceNew,
+		}));
+
+		// The validator set is re-elected without waiting for the end of the epoch
+		ext.run_to_block(4);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(test_helpers::active_validator_ids(), vec![CANDIDATE_1.id, CANDIDATE_3.id]);
+		assert_eq!(Dpos::force_epoch(), Forcing::NotForcing);
+
+		// The next epoch is planned from the forced epoch
+		assert_eq!(Dpos::next_epoch_start_block(), 4 + TEST_BLOCKS_PER_EPOCH);
+		ext.run_to_block(4 + TEST_BLOCKS_PER_EPOCH - 1);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		ext.run_to_block(4 + TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn should_ok_freeze_epochs_with_force_no_epochs() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::force_no_epochs(RuntimeOrigin::root()));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::EpochForcingSet {
+			mode: Forcing::ForceNone,
+		}));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 500));
+
+		// The active validator set is frozen past the planned end of the epoch
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+		assert_eq!(test_helpers::active_validator_ids(), vec![CANDIDATE_1.id, CANDIDATE_2.id]);
+
+		assert_ok!(Dpos::force_new_epoch(RuntimeOrigin::root()));
+		ext.next_block();
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(test_helpers::active_validator_ids(), vec![CANDIDATE_1.id, CANDIDATE_3.id]);
+	});
+}
+
+#[test]
+fn should_ok_discard_paged_election_with_force_no_epochs() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(5)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 2);
+		assert!(PendingValidatorSet::<Test>::get().is_some());
+
+		assert_ok!(Dpos::force_no_epochs(RuntimeOrigin::root()));
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+		assert_eq!(PagedElectionPhase::<Test>::get(), None);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn should_ok_force_new_epoch_at_every_block() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(3);
+		assert_ok!(Dpos::force_new_epoch_always(RuntimeOrigin::root()));
+
+		ext.run_to_block(4);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		ext.run_to_block(6);
+		assert_eq!(EpochIndex::<Test>::get(), 3);
+		assert_eq!(Dpos::force_epoch(), Forcing::ForceAlways);
+	});
+}
@@ -0,0 +1,87 @@
+use crate::{mock::*, *};
+use constants::*;
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use sp_runtime::{BoundedBTreeMap, DispatchError};
+use std::collections::BTreeMap;
+
+use tests::ros;
+use types::EpochSnapshot;
+
+#[test]
+fn should_failed_invalid_origin() {
+	let ext = TestExtBuilder::default();
+	ext.build().execute_with(|| {
+		assert_noop!(
+			Dpos::force_set_emergency_validators(ros(ACCOUNT_1.id), vec![CANDIDATE_1.id]),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn should_failed_invalid_validator_set() {
+	let ext = TestExtBuilder::default();
+	ext.build().execute_with(|| {
+		assert_noop!(
+			Dpos::force_set_emergency_validators(RuntimeOrigin::root(), vec![]),
+			Error::<Test>::EmptyValidatorSet
+		);
+		assert_noop!(
+			Dpos::force_set_emergency_validators(
+				RuntimeOrigin::root(),
+				vec![CANDIDATE_1.id, CANDIDATE_1.id]
+			),
+			Error::<Test>::DuplicateValidator
+		);
+		assert_noop!(
+			Dpos::force_set_emergency_validators(
+				RuntimeOrigin::root(),
+				vec![CANDIDATE_1.id, ACCOUNT_1.id]
+			),
+			Error::<Test>::CandidateDoesNotExist
+		);
+
+		let too_many_validators =
+			(0..MaxActiveValidators::get() as u64 + 1).map(|i| CANDIDATE_1.id + i).collect();
+		assert_noop!(
+			Dpos::force_set_emergency_validators(RuntimeOrigin::root(), too_many_validators),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn should_ok_install_emergency_validator_set() {
+	let mut ext = TestExtBuilder::default();
+	ext.min_active_validators(2)
+		.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+		.genesis_candidates(vec![(CANDIDATE_1.id, 300), (CANDIDATE_2.id, 200)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_1.id)));
+
+			assert_ok!(Dpos::force_set_emergency_validators(
+				RuntimeOrigin::root(),
+				vec![CANDIDATE_2.id]
+			));
+			System::assert_last_event(RuntimeEvent::Dpos(Event::EmergencyValidatorSetInstalled {
+				validators: vec![CANDIDATE_2.id],
+			}));
+			assert_eq!(Dpos::active_validators().to_vec(), vec![(CANDIDATE_2.id, 200, 200)]);
+			assert_eq!(
+				Dpos::last_epoch_snapshot(),
+				Some(EpochSnapshot {
+					validators: BoundedBTreeMap::try_from(BTreeMap::from_iter(vec![(
+						CANDIDATE_2.id,
+						200
+					)]))
+					.unwrap(),
+					total_delegations: Default::default(),
+				})
+			);
+
+			// The election keeps failing, the emergency set stays in place
+			ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+			assert_eq!(Dpos::active_validators().to_vec(), vec![(CANDIDATE_2.id, 200, 200)]);
+		});
+}
@@ -125,7 +125,9 @@ fn should_ok_undelegate_all_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 0,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 		});
@@ -166,7 +168,9 @@ fn should_ok_undelegate_partial_amount() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 125,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 
@@ -214,7 +218,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_4.id), ACCOUNT_4.balance - 200 + 75);
@@ -247,7 +253,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75 - 199,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance - 300 + 199);
@@ -275,7 +283,9 @@ fn should_ok_multiple_undelegate_both_all_and_partial() {
 				Some(CandidateDetail {
 					bond: 40,
 					total_delegations: 500 - 75 - 199 - 101,
-					status: types::ValidatorStatus::Online
+					status: types::ValidatorStatus::Online,
+					commission: Default::default(),
+					commission_updated_at: None,
 				})
 			);
 			assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance - 300 + 199 + 101);
@@ -3,7 +3,6 @@ use frame::deps::frame_support::assert_ok;
 use crate::{
 	constants::{AccountId, Balance},
 	mock::*,
-	pallet,
 	tests::ros,
 	types::{
 		CandidateDelegationSet, CandidateDetail, DelegationInfo, EpochSnapshot, ValidatorStatus,
@@ -24,7 +23,9 @@ pub fn register_new_candidate(
 		Some(CandidateDetail {
 			bond: hold_amount,
 			total_delegations: 0,
-			status: ValidatorStatus::Online
+			status: ValidatorStatus::Online,
+			commission: Default::default(),
+			commission_updated_at: None,
 		})
 	);
 	assert_eq!(Balances::free_balance(candidate), balance - hold_amount);
@@ -34,7 +35,7 @@ pub fn register_new_candidate(
 		hold_amount
 	);
 
-	assert_eq!(CandidateDelegators::<Test>::get(&candidate), vec![]);
+	assert_eq!(CandidateDelegators::<Test>::get(candidate), vec![]);
 
 	// Assert that the correct event was deposited
 	System::assert_last_event(RuntimeEvent::Dpos(Event::CandidateRegistered {
@@ -63,31 +64,31 @@ pub fn delegate_candidate(delegator: AccountId, candidate: AccountId, amount: Ba
 	Dpos::do_try_state();
 }
 
-pub fn get_delegator_commission() -> u32 {
-	<Test as pallet::Config>::DelegatorCommission::get()
-}
-
-pub fn get_author_commission() -> u32 {
-	<Test as pallet::Config>::AuthorCommission::get()
-}
-
 pub fn get_genesis_epoch_snapshot(
 	active_validator_set: CandidateDelegationSet<Test>,
 ) -> EpochSnapshot<Test> {
 	let mut epoch_snapshot = EpochSnapshot::<Test>::default();
 	for (active_validator_id, bond, _) in active_validator_set.to_vec().iter() {
-		epoch_snapshot.add_validator(active_validator_id.clone(), bond.clone());
+		epoch_snapshot.add_validator(*active_validator_id, *bond);
 		for delegator in CandidateDelegators::<Test>::get(active_validator_id) {
 			if let Some(delegation_info) =
-				DelegationInfos::<Test>::get(&delegator, &active_validator_id)
+				DelegationInfos::<Test>::get(delegator, active_validator_id)
 			{
-				epoch_snapshot.add_delegator(
-					delegator,
-					active_validator_id.clone(),
-					delegation_info.amount,
-				);
+				epoch_snapshot.add_delegation(*active_validator_id, delegation_info.amount);
 			}
 		}
 	}
 	epoch_snapshot
 }
+
+/// The account IDs of the validators, sorted
+pub fn sorted_ids(validators: &[(AccountId, Balance, Balance)]) -> Vec<AccountId> {
+	let mut ids = validators.iter().map(|(id, _, _)| *id).collect::<Vec<AccountId>>();
+	ids.sort();
+	ids
+}
+
+/// The account IDs of the active validators, sorted
+pub fn active_validator_ids() -> Vec<AccountId> {
+	sorted_ids(&Dpos::active_validators())
+}
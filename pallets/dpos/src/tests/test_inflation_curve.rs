@@ -0,0 +1,194 @@
+use crate::{mock::*, types::InflationCurve, *};
+use constants::{CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::{
+	frame_support::{assert_noop, assert_ok},
+	sp_runtime::{DispatchError, Perbill},
+};
+use tests::ros;
+
+const ONE_DAY: u64 = 24 * 60 * 60 * 1000;
+
+fn test_inflation_curve() -> InflationCurve {
+	InflationCurve {
+		ideal_stake: Perbill::from_percent(50),
+		min_inflation: Perbill::from_rational(25u32, 1000),
+		max_inflation: Perbill::from_percent(10),
+		falloff: Perbill::from_percent(5),
+	}
+}
+
+fn assert_approx_eq(actual: Perbill, expected: Perbill) {
+	assert!(
+		actual.deconstruct().abs_diff(expected.deconstruct()) <= 100_000,
+		"{:?} is not close to {:?}",
+		actual,
+		expected
+	);
+}
+
+#[test]
+fn should_ok_compute_annual_inflation_along_the_curve() {
+	let curve = test_inflation_curve();
+
+	// The inflation increases linearly up to the ideal stake
+	assert_eq!(curve.annual_inflation(Perbill::zero()), curve.min_inflation);
+	assert_eq!(
+		curve.annual_inflation(Perbill::from_percent(25)),
+		Perbill::from_rational(625u32, 10000)
+	);
+	assert_eq!(curve.annual_inflation(Perbill::from_percent(50)), curve.max_inflation);
+
+	// The inflation above the minimum halves every falloff above the ideal stake
+	assert_approx_eq(
+		curve.annual_inflation(Perbill::from_percent(55)),
+		Perbill::from_rational(625u32, 10000),
+	);
+	assert_approx_eq(
+		curve.annual_inflation(Perbill::from_percent(60)),
+		Perbill::from_rational(4375u32, 100000),
+	);
+	assert_approx_eq(
+		curve.annual_inflation(Perbill::from_rational(525u32, 1000)),
+		Perbill::from_rational(25u32, 1000) +
+			Perbill::from_rational(75u32, 1000) * Perbill::from_rational(70710678u32, 100000000),
+	);
+	assert_approx_eq(curve.annual_inflation(Perbill::one()), curve.min_inflation);
+}
+
+#[test]
+fn should_ok_compute_annual_inflation_within_perbill_precision() {
+	let curve = test_inflation_curve();
+	let max_above_min = (curve.max_inflation - curve.min_inflation).deconstruct() as f64;
+	for stake_ratio in (500_000_000u32..=1_000_000_000).step_by(1_234_567) {
+		let exponent = (stake_ratio - curve.ideal_stake.deconstruct()) as f64 /
+			curve.falloff.deconstruct() as f64;
+		let expected =
+			curve.min_inflation.deconstruct() as f64 + max_above_min * (-exponent).exp2();
+		let actual = curve.annual_inflation(Perbill::from_parts(stake_ratio));
+		assert!(
+			(actual.deconstruct() as f64 - expected).abs() <= 2.0,
+			"{:?} is not close to {} at {}",
+			actual,
+			expected,
+			stake_ratio
+		);
+	}
+}
+
+#[test]
+fn should_ok_decrease_annual_inflation_above_ideal_stake() {
+	let curve = test_inflation_curve();
+	let falloff = curve.falloff.deconstruct();
+	let mut previous = curve.max_inflation;
+	for stake_ratio in (500_000_000u32..=1_000_000_000).step_by(99_991) {
+		let inflation = curve.annual_inflation(Perbill::from_parts(stake_ratio));
+		assert!(inflation <= previous, "The inflation increases at {}", stake_ratio);
+		previous = inflation;
+	}
+
+	// The inflation does not increase between consecutive staking ratios, including where the
+	// exponent reaches the next integer
+	for halvings in 1..10 {
+		let boundary = curve.ideal_stake.deconstruct() + halvings * falloff;
+		let mut previous = curve.annual_inflation(Perbill::from_parts(boundary - 1_000));
+		for stake_ratio in (boundary - 999)..=(boundary + 1_000) {
+			let inflation = curve.annual_inflation(Perbill::from_parts(stake_ratio));
+			assert!(inflation <= previous, "The inflation increases at {}", stake_ratio);
+			previous = inflation;
+		}
+	}
+}
+
+#[test]
+fn should_ok_set_inflation_curve() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.expected_block_time(ONE_DAY)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_inflation_curve(RuntimeOrigin::root(), Some(test_inflation_curve())));
+		assert_eq!(Dpos::inflation_curve(), Some(test_inflation_curve()));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::InflationCurveSet {
+			curve: Some(test_inflation_curve()),
+		}));
+
+		assert_ok!(Dpos::set_inflation_curve(RuntimeOrigin::root(), None));
+		assert_eq!(Dpos::inflation_curve(), None);
+	});
+}
+
+#[test]
+fn should_failed_set_invalid_inflation_curve() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.expected_block_time(ONE_DAY)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::set_inflation_curve(ros(CANDIDATE_1.id), Some(test_inflation_curve())),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Dpos::set_inflation_curve(
+				RuntimeOrigin::root(),
+				Some(InflationCurve { falloff: Perbill::zero(), ..test_inflation_curve() })
+			),
+			Error::<Test>::InvalidInflationCurve
+		);
+		assert_noop!(
+			Dpos::set_inflation_curve(
+				RuntimeOrigin::root(),
+				Some(InflationCurve {
+					min_inflation: Perbill::from_percent(20),
+					..test_inflation_curve()
+				})
+			),
+			Error::<Test>::InvalidInflationCurve
+		);
+	});
+}
+
+#[test]
+fn should_ok_allocate_epoch_reward_budget_from_inflation_curve() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.expected_block_time(ONE_DAY)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_inflation_curve(RuntimeOrigin::root(), Some(test_inflation_curve())));
+		let total_issuance = Balances::total_issuance();
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		// The budget depends on the total issuance and the total staked in the snapshot
+		let expected_budget = test_inflation_curve().epoch_budget(
+			600,
+			total_issuance,
+			TEST_BLOCKS_PER_EPOCH * ONE_DAY,
+		);
+		assert!(expected_budget > 0);
+		assert_eq!(Dpos::epoch_info(0).unwrap().total_rewards, expected_budget);
+
+		// The fixed epoch reward is used again without the inflation curve
+		assert_ok!(Dpos::set_inflation_curve(RuntimeOrigin::root(), None));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(Dpos::epoch_info(1).unwrap().total_rewards, 1_000);
+	});
+}
@@ -0,0 +1,257 @@
+use crate::{
+	migrations::{
+		v1::MigrateV0ToV1,
+		v2::{MigrateV1ToV2, OldCandidateDetail},
+		v3::MigrateV2ToV3,
+		v4::{MigrateV3ToV4, OldDelayActionRequest},
+		v5::{LastEpochSnapshot, MigrateV4ToV5, OldEpochSnapshot},
+	},
+	mock::*,
+	types::{DelayActionType, ValidatorRewardPool, ValidatorStatus},
+	*,
+};
+use constants::{
+	ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{
+	assert_ok,
+	storage::unhashed,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::Perbill;
+use tests::ros;
+
+#[test]
+fn should_ok_seed_next_epoch_start_block_from_epoch_duration() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(0).put::<Dpos>();
+		NextEpochStartBlock::<Test>::kill();
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH * 2 + 3);
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Dpos::on_chain_storage_version(), 1);
+		assert_eq!(NextEpochStartBlock::<Test>::get(), TEST_BLOCKS_PER_EPOCH * 3);
+
+		// The migration only runs once
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH * 3 + 1);
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(NextEpochStartBlock::<Test>::get(), TEST_BLOCKS_PER_EPOCH * 3);
+	});
+}
+
+#[test]
+fn should_ok_seed_current_block_on_epoch_boundary() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(0).put::<Dpos>();
+		NextEpochStartBlock::<Test>::kill();
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH * 2);
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(NextEpochStartBlock::<Test>::get(), TEST_BLOCKS_PER_EPOCH * 2);
+
+		// The epoch moves in the block where the old schedule would have moved it
+		Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(NextEpochStartBlock::<Test>::get(), TEST_BLOCKS_PER_EPOCH * 3);
+	});
+}
+
+#[test]
+fn should_ok_translate_candidates_without_commission() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(1).put::<Dpos>();
+		for (candidate, bond, status) in [
+			(CANDIDATE_1.id, 300, ValidatorStatus::Online),
+			(CANDIDATE_2.id, 200, ValidatorStatus::Offline),
+			(CANDIDATE_3.id, 100, ValidatorStatus::Online),
+		] {
+			unhashed::put(
+				&CandidatePool::<Test>::hashed_key_for(candidate),
+				&OldCandidateDetail::<Test> { bond, total_delegations: 50, status },
+			);
+		}
+
+		MigrateV1ToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(Dpos::on_chain_storage_version(), 2);
+		assert_eq!(CandidatePool::<Test>::count(), 3);
+		let candidate_detail = CandidatePool::<Test>::get(CANDIDATE_2.id).unwrap();
+		assert_eq!(candidate_detail.bond, 200);
+		assert_eq!(candidate_detail.total_delegations, 50);
+		assert_eq!(candidate_detail.status, ValidatorStatus::Offline);
+		assert_eq!(candidate_detail.commission, Perbill::zero());
+		assert_eq!(candidate_detail.commission_updated_at, None);
+
+		// The translated candidates can set their commission
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_1.id), Perbill::from_percent(5)));
+		assert_eq!(
+			CandidatePool::<Test>::get(CANDIDATE_1.id).unwrap().commission,
+			Perbill::from_percent(5)
+		);
+	});
+}
+
+#[test]
+fn should_ok_put_registered_candidates_into_bags() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(2).put::<Dpos>();
+		let _ = CandidateBags::<Test>::clear(u32::MAX, None);
+		let _ = CandidateBagOf::<Test>::clear(u32::MAX, None);
+		assert_eq!(Dpos::select_active_validator_set(), vec![]);
+
+		MigrateV2ToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(Dpos::on_chain_storage_version(), 3);
+		for (candidate, total_stake) in
+			[(CANDIDATE_1.id, 300), (CANDIDATE_2.id, 200), (CANDIDATE_3.id, 100)]
+		{
+			let threshold = Dpos::bag_threshold_of(total_stake);
+			assert_eq!(CandidateBagOf::<Test>::get(candidate), Some(threshold));
+			assert!(CandidateBags::<Test>::get(threshold).contains(&candidate));
+		}
+		assert_eq!(
+			Dpos::select_active_validator_set(),
+			vec![
+				(CANDIDATE_1.id, 300, 300),
+				(CANDIDATE_2.id, 200, 200),
+				(CANDIDATE_3.id, 100, 100)
+			]
+		);
+	});
+}
+
+#[test]
+fn should_ok_translate_delay_action_requests_without_timestamp() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(3).put::<Dpos>();
+		unhashed::put(
+			&DelayActionRequests::<Test>::hashed_key_for(
+				CANDIDATE_1.id,
+				DelayActionType::CandidateLeaved,
+			),
+			&OldDelayActionRequest::<Test> {
+				created_at: 1,
+				delay_for: 10,
+				amount: None,
+				target: None,
+			},
+		);
+		unhashed::put(
+			&DelayActionRequests::<Test>::hashed_key_for(
+				ACCOUNT_6.id,
+				DelayActionType::CandidateUndelegated,
+			),
+			&OldDelayActionRequest::<Test> {
+				created_at: 2,
+				delay_for: 10,
+				amount: Some(150),
+				target: Some(CANDIDATE_2.id),
+			},
+		);
+
+		MigrateV3ToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(Dpos::on_chain_storage_version(), 4);
+		let request =
+			DelayActionRequests::<Test>::get(ACCOUNT_6.id, DelayActionType::CandidateUndelegated)
+				.unwrap();
+		assert_eq!(request.created_at, 2);
+		assert_eq!(request.delay_for, 10);
+		assert_eq!(request.created_at_time, None);
+		assert_eq!(request.amount, Some(150));
+		assert_eq!(request.target, Some(CANDIDATE_2.id));
+		assert_eq!(
+			DelayActionRequests::<Test>::get(CANDIDATE_1.id, DelayActionType::CandidateLeaved)
+				.unwrap()
+				.created_at_time,
+			None
+		);
+	});
+}
+
+#[test]
+fn should_ok_move_last_epoch_snapshot_into_epoch_snapshots() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.build()
+	.execute_with(|| {
+		StorageVersion::new(4).put::<Dpos>();
+		EpochIndex::<Test>::put(2);
+		LastEpochSnapshot::<Test>::put(OldEpochSnapshot::<Test> {
+			validators: [(CANDIDATE_1.id, 300), (CANDIDATE_2.id, 200)].into_iter().collect(),
+			delegations: [
+				((ACCOUNT_5.id, CANDIDATE_1.id), 100),
+				((ACCOUNT_6.id, CANDIDATE_1.id), 50),
+				((ACCOUNT_6.id, CANDIDATE_2.id), 40),
+			]
+			.into_iter()
+			.collect(),
+		});
+
+		MigrateV4ToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(Dpos::on_chain_storage_version(), 5);
+		assert!(!LastEpochSnapshot::<Test>::exists());
+
+		let epoch_snapshot = EpochSnapshots::<Test>::get(2).unwrap();
+		assert_eq!(
+			epoch_snapshot.validators.into_inner().into_iter().collect::<Vec<_>>(),
+			vec![(CANDIDATE_1.id, 300), (CANDIDATE_2.id, 200)]
+		);
+		assert_eq!(
+			epoch_snapshot.total_delegations.into_inner().into_iter().collect::<Vec<_>>(),
+			vec![(CANDIDATE_1.id, 150), (CANDIDATE_2.id, 40)]
+		);
+		assert_eq!(EpochDelegations::<Test>::get((2, ACCOUNT_5.id, CANDIDATE_1.id)), Some(100));
+		assert_eq!(EpochDelegations::<Test>::get((2, ACCOUNT_6.id, CANDIDATE_1.id)), Some(50));
+		assert_eq!(EpochDelegations::<Test>::get((2, ACCOUNT_6.id, CANDIDATE_2.id)), Some(40));
+
+		// The reward pools are opened so that the blocks of the epoch are rewarded
+		assert_eq!(
+			ValidatorRewardPools::<Test>::get(2, CANDIDATE_1.id),
+			Some(ValidatorRewardPool::new(300, 150, Perbill::zero()))
+		);
+		assert_eq!(
+			ValidatorRewardPools::<Test>::get(2, CANDIDATE_2.id),
+			Some(ValidatorRewardPool::new(200, 40, Perbill::zero()))
+		);
+		assert_eq!(ValidatorRewardPools::<Test>::get(2, CANDIDATE_3.id), None);
+	});
+}
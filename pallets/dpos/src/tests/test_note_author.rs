@@ -0,0 +1,102 @@
+use crate::{mock::*, *};
+use constants::{
+	ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{assert_ok, traits::Hooks};
+use pallet_authorship::EventHandler;
+use tests::{ros, test_helpers};
+
+#[test]
+fn should_ok_reward_noted_block_author() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.find_author_disabled()
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 150));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		// Nothing is rewarded without a block author
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+
+		// The block reward of the stake is shared pro-rata to the bond and the delegation, the
+		// remainder of the rounding goes to the validator
+		<Dpos as EventHandler<_, _>>::note_author(CANDIDATE_1.id);
+		assert_eq!(Dpos::block_reward(450), 13);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 10);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 3);
+		assert_eq!(Dpos::reward_points(CANDIDATE_2.id), 0);
+
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH + 1);
+		<Dpos as EventHandler<_, _>>::note_author(CANDIDATE_1.id);
+		assert_eq!(
+			Dpos::reward_points(CANDIDATE_1.id) + Dpos::reward_points(ACCOUNT_6.id),
+			Dpos::block_reward(450) * 2
+		);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 7);
+	});
+}
+
+#[test]
+fn should_ok_ignore_noted_author_outside_active_set() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.find_author_disabled()
+	.build()
+	.execute_with(|| {
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 50);
+
+		<Dpos as EventHandler<_, _>>::note_author(CANDIDATE_4.id);
+		assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_reward_block_once_with_find_author_and_noted_author() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		// The author found with `FindAuthor` is rewarded in `on_initialize`, the same block noted
+		// by `pallet_authorship` is not rewarded again
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH + 1);
+		let block_author = Dpos::find_author().unwrap();
+		let reward_points = Dpos::reward_points(block_author);
+		Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH + 1);
+		let rewarded_points = Dpos::reward_points(block_author);
+		assert!(rewarded_points > reward_points);
+		<Dpos as EventHandler<_, _>>::note_author(block_author);
+		assert_eq!(Dpos::reward_points(block_author), rewarded_points);
+
+		// The author noted first is rewarded once as well
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH + 2);
+		let block_author = Dpos::find_author().unwrap();
+		let reward_points = Dpos::reward_points(block_author);
+		<Dpos as EventHandler<_, _>>::note_author(block_author);
+		let rewarded_points = Dpos::reward_points(block_author);
+		assert!(rewarded_points > reward_points);
+		Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH + 2);
+		assert_eq!(Dpos::reward_points(block_author), rewarded_points);
+	});
+}
@@ -0,0 +1,284 @@
+use crate::{mock::*, types::ElectionPhase, weights::WeightInfo, *};
+use constants::{
+	ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, CANDIDATE_5,
+	TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{assert_ok, traits::Hooks};
+use tests::ros;
+
+#[test]
+fn should_ok_read_candidates_page_by_page_before_epoch_end() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(8)
+	.snapshot_page_size(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_5.id, 450));
+		let elected_validator_set = Dpos::select_active_validator_set();
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 9);
+		assert_eq!(PagedElectionPhase::<Test>::get(), None);
+
+		// The election starts eight blocks before the end of the epoch with the first page of
+		// candidates, read from the bags with the highest thresholds
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 8);
+		assert_eq!(
+			PagedElectionPhase::<Test>::get(),
+			Some(ElectionPhase::Candidates { bag: 4, position: 1 })
+		);
+		assert_eq!(
+			ElectableCandidates::<Test>::get().to_vec(),
+			vec![(CANDIDATE_5.id, 100, 550), (CANDIDATE_1.id, 500, 500)]
+		);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+
+		// The bag is read over two pages and the weight of the page is accounted in the block
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH - 7);
+		assert_eq!(
+			Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH - 7),
+			<Test as Config>::WeightInfo::read_electable_candidates(2)
+		);
+		assert_eq!(
+			PagedElectionPhase::<Test>::get(),
+			Some(ElectionPhase::Candidates { bag: 5, position: 0 })
+		);
+
+		// The top staked election does not need the delegations, the election runs once all the
+		// candidates are read
+		ext.next_block();
+		assert_eq!(PagedElectionPhase::<Test>::get(), Some(ElectionPhase::Elect));
+		assert_eq!(ElectableCandidates::<Test>::get().len(), 5);
+		assert_eq!(ElectableCandidateDelegations::<Test>::iter().count(), 0);
+
+		ext.next_block();
+		assert_eq!(PagedElectionPhase::<Test>::get(), None);
+		assert_eq!(ElectableCandidates::<Test>::get().len(), 0);
+		assert_eq!(PendingValidatorSet::<Test>::get().unwrap().to_vec(), elected_validator_set);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::active_validators().to_vec(), elected_validator_set);
+	});
+}
+
+#[test]
+fn should_ok_read_delegations_page_by_page_when_the_election_needs_them() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 100),
+		(CANDIDATE_2.id, 100),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::SequentialPhragmen)
+	.election_lookahead(6)
+	.snapshot_page_size(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 4900));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_2.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_5.id), CANDIDATE_3.id, 400));
+		let elected_validator_set = Dpos::select_active_validator_set();
+
+		// The candidates are read over two pages
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 5);
+		assert_eq!(
+			PagedElectionPhase::<Test>::get(),
+			Some(ElectionPhase::Delegations { cursor: 0 })
+		);
+		assert_eq!(
+			ElectableCandidates::<Test>::get().to_vec(),
+			vec![
+				(CANDIDATE_1.id, 100, 5000),
+				(CANDIDATE_3.id, 100, 500),
+				(CANDIDATE_2.id, 100, 200)
+			]
+		);
+
+		// Then the delegations of two candidates are read in every page
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH - 4);
+		assert_eq!(
+			Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH - 4),
+			<Test as Config>::WeightInfo::read_candidate_delegations(2, 2)
+		);
+		assert_eq!(
+			PagedElectionPhase::<Test>::get(),
+			Some(ElectionPhase::Delegations { cursor: 2 })
+		);
+		assert_eq!(
+			ElectableCandidateDelegations::<Test>::get(CANDIDATE_1.id).to_vec(),
+			vec![(ACCOUNT_6.id, 4900)]
+		);
+		assert_eq!(
+			ElectableCandidateDelegations::<Test>::get(CANDIDATE_3.id).to_vec(),
+			vec![(ACCOUNT_5.id, 400)]
+		);
+
+		ext.next_block();
+		assert_eq!(PagedElectionPhase::<Test>::get(), Some(ElectionPhase::Elect));
+
+		// The election runs on the delegations read by the previous pages
+		ext.next_block();
+		assert_eq!(PendingValidatorSet::<Test>::get().unwrap().to_vec(), elected_validator_set);
+		assert_eq!(ElectableCandidateDelegations::<Test>::iter().count(), 0);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::active_validators().to_vec(), elected_validator_set);
+	});
+}
+
+#[test]
+fn should_ok_capture_snapshot_page_by_page_before_epoch_end() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(8)
+	.snapshot_page_size(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_5.id, 450));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 6);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+
+		// The election starts eight blocks before the end of the epoch, the candidates are read in
+		// three pages and the pending set is elected in the fourth block
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 5);
+		let pending_validator_set = PendingValidatorSet::<Test>::get().unwrap().to_vec();
+		assert_eq!(pending_validator_set, Dpos::select_active_validator_set());
+		assert_eq!(pending_validator_set[0], (CANDIDATE_5.id, 100, 550));
+		assert_eq!(SnapshotPageCursor::<Test>::get(), 0);
+		assert_eq!(PendingSnapshotPages::<Test>::iter().count(), 0);
+
+		// Every following block captures one page of the snapshot under its own key
+		ext.next_block();
+		assert_eq!(SnapshotPageCursor::<Test>::get(), 2);
+		let pending_snapshot = PendingSnapshotPages::<Test>::get(0).unwrap();
+		assert_eq!(pending_snapshot.validators.len(), 2);
+		assert_eq!(pending_snapshot.total_delegations_of(&CANDIDATE_5.id), 450);
+		assert_eq!(EpochDelegations::<Test>::get((1, ACCOUNT_6.id, CANDIDATE_5.id)), Some(450));
+
+		// The weight of a page is accounted in the block
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH - 3);
+		assert_eq!(
+			Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH - 3),
+			<Test as Config>::WeightInfo::capture_snapshot_page(2, 0)
+		);
+		assert_eq!(SnapshotPageCursor::<Test>::get(), 4);
+		assert_eq!(PendingSnapshotPages::<Test>::get(1).unwrap().validators.len(), 2);
+		assert_eq!(PendingSnapshotPages::<Test>::get(0), Some(pending_snapshot));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		assert_eq!(SnapshotPageCursor::<Test>::get(), 5);
+
+		// The pending set is installed at the epoch boundary
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::active_validators().to_vec(), pending_validator_set);
+		assert_eq!(
+			Dpos::last_epoch_snapshot(),
+			Some(Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &pending_validator_set))
+		);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+		assert_eq!(PendingSnapshotPages::<Test>::iter().count(), 0);
+		assert_eq!(SnapshotPageCursor::<Test>::get(), 0);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn should_ok_drop_offline_candidates_from_pending_set() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(8)
+	.snapshot_page_size(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_5.id, 50));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		assert_eq!(PendingValidatorSet::<Test>::get().unwrap().len(), 5);
+
+		// Candidate turns offline after the pending set is elected and its snapshot is captured
+		assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_5.id)));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		let active_validator_set = Dpos::active_validators().to_vec();
+		assert_eq!(active_validator_set.len(), 4);
+		assert!(!active_validator_set
+			.iter()
+			.any(|(validator, _, _)| *validator == CANDIDATE_5.id));
+
+		let epoch_snapshot = Dpos::last_epoch_snapshot().unwrap();
+		assert_eq!(
+			epoch_snapshot,
+			Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &active_validator_set)
+		);
+		assert!(!epoch_snapshot.total_delegations.contains_key(&CANDIDATE_5.id));
+		assert_eq!(Dpos::reward_pool(1, CANDIDATE_5.id), None);
+	});
+}
+
+#[test]
+fn should_ok_capture_remaining_pages_at_epoch_boundary() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(1)
+	.snapshot_page_size(1)
+	.build()
+	.execute_with(|| {
+		let pending_validator_set = Dpos::select_active_validator_set();
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		assert_eq!(
+			PagedElectionPhase::<Test>::get(),
+			Some(ElectionPhase::Candidates { bag: 4, position: 1 })
+		);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+
+		// Only the first candidate could be read before the epoch boundary, the remaining pages
+		// are processed when the election is finalized
+		System::set_block_number(TEST_BLOCKS_PER_EPOCH);
+		let weight = Dpos::on_initialize(TEST_BLOCKS_PER_EPOCH);
+		assert!(weight.all_gte(
+			<Test as Config>::WeightInfo::read_electable_candidates(1)
+				.saturating_mul(4)
+				.saturating_add(<Test as Config>::WeightInfo::elect_validators(5, 0))
+				.saturating_add(
+					<Test as Config>::WeightInfo::capture_snapshot_page(1, 0).saturating_mul(5)
+				)
+		));
+		assert_eq!(PagedElectionPhase::<Test>::get(), None);
+		assert_eq!(Dpos::active_validators().to_vec(), pending_validator_set);
+		assert_eq!(
+			Dpos::last_epoch_snapshot(),
+			Some(Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &pending_validator_set))
+		);
+	});
+}
@@ -0,0 +1,185 @@
+use crate::{mock::*, *};
+use constants::{ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::{
+	frame_support::{assert_noop, assert_ok},
+	sp_runtime::Perbill,
+};
+use tests::ros;
+
+const EPOCH_REWARD: u128 = 1_000;
+
+#[test]
+fn should_ok_earn_points_for_authored_blocks() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		// The validators earn points instead of rewards for the blocks they author
+		let epoch_points = Dpos::epoch_reward_points(0);
+		assert!(epoch_points.total > 0);
+		assert_eq!(
+			epoch_points.total,
+			[CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+				.iter()
+				.map(|validator| epoch_points.points_of(validator))
+				.sum::<u32>()
+		);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+		assert_eq!(Dpos::epoch_info(0).unwrap().total_rewards, 0);
+
+		// The epoch reward is allocated when the epoch ends
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::epoch_info(0).unwrap().total_rewards, EPOCH_REWARD);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::EpochEnded {
+			epoch: 0,
+			at_block: TEST_BLOCKS_PER_EPOCH,
+			total_rewards: EPOCH_REWARD,
+		}));
+	});
+}
+
+#[test]
+fn should_ok_payout_epoch_to_validator_and_delegators() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+
+		let epoch_points = Dpos::epoch_reward_points(1);
+		let total_reward =
+			Perbill::from_rational(epoch_points.points_of(&CANDIDATE_3.id), epoch_points.total) *
+				EPOCH_REWARD;
+		let delegator_rewards = Perbill::from_rational(250u128, 350) * total_reward;
+		let validator_reward = total_reward - delegator_rewards;
+
+		// Anyone can pay out the rewards of the validator
+		assert_ok!(Dpos::payout_epoch(ros(CANDIDATE_1.id), CANDIDATE_3.id, 1));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::EpochPaidOut {
+			epoch: 1,
+			validator: CANDIDATE_3.id,
+			validator_reward,
+			delegator_rewards,
+		}));
+		assert_eq!(Dpos::epoch_payout(1, CANDIDATE_3.id), Some(total_reward));
+		assert_eq!(Dpos::reward_points(CANDIDATE_3.id), validator_reward);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), delegator_rewards);
+
+		let balance_before = Balances::free_balance(ACCOUNT_6.id);
+		assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), balance_before + delegator_rewards);
+	});
+}
+
+#[test]
+fn should_ok_split_epoch_reward_by_points() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		for validator in [CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id] {
+			assert_ok!(Dpos::payout_epoch(ros(ACCOUNT_6.id), validator, 0));
+		}
+		let total_paid_out = [CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+			.into_iter()
+			.map(Dpos::reward_points)
+			.sum::<u128>();
+		assert!(total_paid_out <= EPOCH_REWARD);
+		assert!(EPOCH_REWARD - total_paid_out < 3);
+	});
+}
+
+#[test]
+fn should_failed_payout_epoch_twice() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		assert_ok!(Dpos::payout_epoch(ros(ACCOUNT_6.id), CANDIDATE_1.id, 0));
+		assert_noop!(
+			Dpos::payout_epoch(ros(ACCOUNT_6.id), CANDIDATE_1.id, 0),
+			Error::<Test>::EpochAlreadyPaidOut
+		);
+	});
+}
+
+#[test]
+fn should_failed_payout_epoch_not_ended() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		assert_noop!(
+			Dpos::payout_epoch(ros(ACCOUNT_6.id), CANDIDATE_1.id, 0),
+			Error::<Test>::EpochNotEnded
+		);
+	});
+}
+
+#[test]
+fn should_failed_payout_epoch_without_reward() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(EPOCH_REWARD)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(1)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+
+		// Not a validator of the epoch
+		assert_noop!(
+			Dpos::payout_epoch(ros(ACCOUNT_6.id), ACCOUNT_6.id, 2),
+			Error::<Test>::NoEpochRewardFound
+		);
+		// The epoch is pruned from the history
+		assert_noop!(
+			Dpos::payout_epoch(ros(ACCOUNT_6.id), CANDIDATE_1.id, 0),
+			Error::<Test>::NoEpochRewardFound
+		);
+		assert!(!EpochRewardPoints::<Test>::contains_key(0));
+	});
+}
@@ -0,0 +1,185 @@
+use crate::{mock::*, types::RewardSource, weights::WeightInfo, *};
+use constants::{
+	ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::{
+	frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo},
+	sp_runtime::BoundedVec,
+};
+use tests::ros;
+
+fn batch(accounts: Vec<u64>) -> BoundedVec<u64, MaxPayoutBatch> {
+	BoundedVec::try_from(accounts).unwrap()
+}
+
+#[test]
+fn should_ok_payout_rewards_of_many_accounts() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+
+		let delegator_rewards = Dpos::reward_points(ACCOUNT_6.id);
+		let validator_rewards = Dpos::reward_points(CANDIDATE_3.id);
+		assert!(delegator_rewards > 0 && validator_rewards > 0);
+		let delegator_balance = Balances::free_balance(ACCOUNT_6.id);
+		let validator_balance = Balances::free_balance(CANDIDATE_3.id);
+
+		// Anyone can pay out the rewards on behalf of the accounts
+		assert_ok!(Dpos::payout_rewards(
+			ros(ACCOUNT_5.id),
+			batch(vec![ACCOUNT_6.id, CANDIDATE_3.id])
+		));
+		System::assert_has_event(RuntimeEvent::Dpos(Event::RewardPaidOut {
+			who: ACCOUNT_6.id,
+			paid_by: ACCOUNT_5.id,
+			total_reward: delegator_rewards,
+		}));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardPaidOut {
+			who: CANDIDATE_3.id,
+			paid_by: ACCOUNT_5.id,
+			total_reward: validator_rewards,
+		}));
+
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), delegator_balance + delegator_rewards);
+		assert_eq!(Balances::free_balance(CANDIDATE_3.id), validator_balance + validator_rewards);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+		assert_eq!(Dpos::reward_points(CANDIDATE_3.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_skip_accounts_without_rewards() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+		System::reset_events();
+
+		// The accounts without rewards and the duplicates are skipped
+		assert_ok!(Dpos::payout_rewards(
+			ros(ACCOUNT_5.id),
+			batch(vec![ACCOUNT_6.id, CANDIDATE_1.id, CANDIDATE_1.id])
+		));
+		let paid_out = System::events()
+			.into_iter()
+			.filter(|record| {
+				matches!(record.event, RuntimeEvent::Dpos(Event::RewardPaidOut { .. }))
+			})
+			.count();
+		assert_eq!(paid_out, 1);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_keep_rewards_of_accounts_not_paid_out() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.reward_source(RewardSource::Pot)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+
+		// The batch does not fail when the reward source is insufficient
+		assert_ok!(Dpos::payout_rewards(ros(ACCOUNT_5.id), batch(vec![CANDIDATE_1.id])));
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), rewards);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardPayoutFailed {
+			who: CANDIDATE_1.id,
+			error: Error::<Test>::InsufficientRewardSource.into(),
+		}));
+	});
+}
+
+#[test]
+fn should_failed_payout_rewards_with_empty_batch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::payout_rewards(ros(ACCOUNT_5.id), batch(vec![])),
+			Error::<Test>::EmptyPayoutBatch
+		);
+	});
+}
+
+#[test]
+fn should_ok_scale_payout_rewards_weight_with_batch_size() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		let weight_of = |accounts: Vec<u64>| {
+			RuntimeCall::Dpos(Call::payout_rewards { accounts: batch(accounts) })
+				.get_dispatch_info()
+				.weight
+		};
+		let max_claims = Dpos::max_delegator_reward_claims();
+		assert_eq!(
+			weight_of(vec![ACCOUNT_6.id]),
+			<Test as Config>::WeightInfo::payout_rewards(1, max_claims)
+		);
+		assert_eq!(
+			weight_of(vec![ACCOUNT_5.id, ACCOUNT_6.id, CANDIDATE_1.id]),
+			<Test as Config>::WeightInfo::payout_rewards(3, max_claims * 3)
+		);
+		assert!(weight_of(vec![ACCOUNT_5.id, ACCOUNT_6.id]).any_gt(weight_of(vec![ACCOUNT_6.id])));
+	});
+}
+
+#[test]
+fn should_ok_refund_payout_rewards_weight_of_unread_delegations() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+
+		let post_info =
+			Dpos::payout_rewards(ros(ACCOUNT_5.id), batch(vec![ACCOUNT_6.id, CANDIDATE_3.id]))
+				.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<Test as Config>::WeightInfo::payout_rewards(2, 1))
+		);
+	});
+}
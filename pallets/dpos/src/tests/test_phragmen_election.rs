@@ -0,0 +1,127 @@
+use crate::{mock::*, *};
+use constants::{
+	ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::assert_ok;
+use tests::{ros, test_helpers};
+use types::CandidateDelegationSet;
+
+fn elect_top_staked_candidates(to_elect: u32) -> CandidateDelegationSet<Test> {
+	TopStakeElection::elect(to_elect, Dpos::get_online_candidate_set(), vec![])
+}
+
+#[test]
+fn should_ok_phragmen_spread_backing_across_delegated_candidates() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 100),
+		(CANDIDATE_2.id, 100),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.election_mode(TestElectionMode::SequentialPhragmen)
+	.build()
+	.execute_with(|| {
+		// A whale delegates most of the stake to candidate 1 and a small amount to candidate 2
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 4900));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_2.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_5.id), CANDIDATE_3.id, 400));
+
+		// Top staked election would pick candidate 1 and candidate 3
+		assert_eq!(
+			test_helpers::sorted_ids(&elect_top_staked_candidates(2)),
+			vec![CANDIDATE_1.id, CANDIDATE_3.id]
+		);
+
+		// The whale backs both candidate 1 and 2, so Phragmén elects both of them and spread
+		// the backing evenly
+		let active_validator_set = Dpos::select_active_validator_set();
+		assert_eq!(
+			test_helpers::sorted_ids(&active_validator_set),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id]
+		);
+
+		let backings =
+			active_validator_set.iter().map(|(_, _, backing)| *backing).collect::<Vec<_>>();
+		assert_eq!(backings.iter().sum::<u128>(), 5200);
+		assert!(backings[0].abs_diff(backings[1]) <= 2);
+
+		// Bonds are kept as is in the elected set
+		assert!(active_validator_set.iter().all(|(_, bond, _)| *bond == 100));
+	});
+}
+
+#[test]
+fn should_ok_phragmen_elected_set_is_stored_at_epoch_boundary() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 100),
+		(CANDIDATE_2.id, 100),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::SequentialPhragmen)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 4900));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_2.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_5.id), CANDIDATE_3.id, 400));
+
+		let active_validator_set = Dpos::select_active_validator_set();
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		assert_eq!(Dpos::active_validators().to_vec(), active_validator_set);
+		assert_eq!(
+			Dpos::last_epoch_snapshot(),
+			Some(Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &active_validator_set))
+		);
+	});
+}
+
+#[test]
+fn should_ok_phragmen_without_delegations_elect_top_bonded_candidates() {
+	let mut ext = TestExtBuilder::default();
+	ext.max_active_validators(9)
+		.election_mode(TestElectionMode::SequentialPhragmen)
+		.build()
+		.execute_with(|| {
+			let max_active_validators = <Test as Config>::MaxActiveValidators::get();
+
+			// Without any delegations, candidates are only backed by their own bond
+			let active_validator_set = Dpos::select_active_validator_set();
+			assert_eq!(active_validator_set.len(), max_active_validators as usize);
+			assert_eq!(
+				test_helpers::sorted_ids(&active_validator_set),
+				test_helpers::sorted_ids(&elect_top_staked_candidates(max_active_validators))
+			);
+			assert!(active_validator_set.iter().all(|(_, bond, backing)| bond == backing));
+		});
+}
+
+#[test]
+fn should_ok_read_delegation_edges_only_when_the_election_needs_them() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 100),
+		(CANDIDATE_2.id, 100),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_1.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_2.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_5.id), CANDIDATE_3.id, 100));
+
+		// The top staked election does not read the delegations
+		Dpos::select_active_validator_set();
+		assert_eq!(LastElectionEdges::get(), 0);
+
+		// Phragmén receives every delegation of the online candidates
+		DposElectionMode::set(TestElectionMode::SequentialPhragmen);
+		Dpos::select_active_validator_set();
+		assert_eq!(LastElectionEdges::get(), 3);
+	});
+}
@@ -0,0 +1,108 @@
+use crate::{mock::*, *};
+use constants::{
+	ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, CANDIDATE_5,
+	TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::assert_ok;
+use tests::ros;
+
+#[test]
+fn should_ok_preview_next_validator_set_without_writing_storage() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_4.id, 250));
+		DeferredChallengers::<Test>::put(7);
+
+		let (winners, losers) = Dpos::preview_next_validator_set();
+		assert_eq!(
+			winners,
+			vec![
+				(CANDIDATE_1.id, 500, 500),
+				(CANDIDATE_4.id, 200, 450),
+				(CANDIDATE_2.id, 400, 400)
+			]
+		);
+		// Losers are sorted by the stake they miss to reach the lowest total of the winners
+		assert_eq!(losers, vec![(CANDIDATE_3.id, 300, 100), (CANDIDATE_5.id, 100, 300)]);
+
+		// Nothing is written by the preview
+		assert_eq!(DeferredChallengers::<Test>::get(), 7);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+		assert_eq!(
+			Dpos::active_validators().to_vec(),
+			vec![
+				(CANDIDATE_1.id, 500, 500),
+				(CANDIDATE_2.id, 400, 400),
+				(CANDIDATE_3.id, 300, 300)
+			]
+		);
+
+		// The preview matches the outcome of the election at the end of the epoch
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::active_validators().to_vec(), winners);
+	});
+}
+
+#[test]
+fn should_ok_preview_pending_validator_set_during_paged_election() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(5)
+	.build()
+	.execute_with(|| {
+		// The candidates are read in three pages and the pending set is elected in the fourth
+		// block of the election
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 2);
+		let pending_validator_set = PendingValidatorSet::<Test>::get().unwrap().to_vec();
+
+		// Stake that moves after the election started is only counted in the next election
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_5.id, 450));
+		let (winners, losers) = Dpos::preview_next_validator_set();
+		assert_eq!(winners, pending_validator_set);
+		assert_eq!(losers, vec![(CANDIDATE_5.id, 550, 0), (CANDIDATE_4.id, 200, 100)]);
+	});
+}
+
+#[test]
+fn should_ok_preview_current_validator_set_when_election_fails() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.min_active_validators(4)
+	.build()
+	.execute_with(|| {
+		let active_validators = Dpos::active_validators().to_vec();
+
+		assert_ok!(Dpos::toggle_candidate_status(&CANDIDATE_4.id));
+		assert_ok!(Dpos::toggle_candidate_status(&CANDIDATE_5.id));
+
+		let (winners, losers) = Dpos::preview_next_validator_set();
+		assert_eq!(winners, active_validators);
+		assert_eq!(losers, vec![]);
+	});
+}
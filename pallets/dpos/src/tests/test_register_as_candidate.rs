@@ -89,3 +89,32 @@ fn should_failed_duplicate_candidate() {
 		assert_noop!(Dpos::register_as_candidate(ros(2), 15), Error::<Test>::CandidateAlreadyExist);
 	});
 }
+
+#[test]
+fn should_failed_register_without_session_keys() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![])
+		.epoch_trigger(types::EpochTrigger::Session)
+		.accounts_without_session_keys(vec![CANDIDATE_1.id])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Dpos::register_as_candidate(ros(CANDIDATE_1.id), 500),
+				Error::<Test>::MissingSessionKeys
+			);
+			test_helpers::register_new_candidate(CANDIDATE_2.id, CANDIDATE_2.balance, 500);
+
+			Dpos::do_try_state()
+		});
+}
+
+#[test]
+fn should_ok_register_without_session_keys_in_block_number_mode() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![])
+		.accounts_without_session_keys(vec![CANDIDATE_1.id])
+		.build()
+		.execute_with(|| {
+			test_helpers::register_new_candidate(CANDIDATE_1.id, CANDIDATE_1.balance, 500);
+		});
+}
@@ -0,0 +1,186 @@
+use crate::{mock::*, types::RewardDestination, *};
+use constants::{
+	ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::assert_ok;
+use tests::ros;
+
+#[test]
+fn should_ok_set_reward_destination() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_eq!(Dpos::reward_destination(ACCOUNT_6.id), RewardDestination::Free);
+
+		assert_ok!(Dpos::set_reward_destination(ros(ACCOUNT_6.id), RewardDestination::Staked));
+		assert_eq!(Dpos::reward_destination(ACCOUNT_6.id), RewardDestination::Staked);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardDestinationSet {
+			who: ACCOUNT_6.id,
+			destination: RewardDestination::Staked,
+		}));
+
+		// The default destination is not stored
+		assert_ok!(Dpos::set_reward_destination(ros(ACCOUNT_6.id), RewardDestination::Free));
+		assert!(!RewardDestinations::<Test>::contains_key(ACCOUNT_6.id));
+	});
+}
+
+#[test]
+fn should_ok_claim_reward_to_another_account() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_reward_destination(
+			ros(CANDIDATE_1.id),
+			RewardDestination::Account(ACCOUNT_5.id)
+		));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+		let balance_before = Balances::free_balance(CANDIDATE_1.id);
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_1.id)));
+
+		assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance + rewards);
+		assert_eq!(Balances::free_balance(CANDIDATE_1.id), balance_before);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_stake_candidate_rewards_into_bond() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_reward_destination(ros(CANDIDATE_3.id), RewardDestination::Staked));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		let rewards = Dpos::reward_points(CANDIDATE_3.id);
+		assert!(rewards > 0);
+		let balance_before = Balances::free_balance(CANDIDATE_3.id);
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_3.id)));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardStaked {
+			who: CANDIDATE_3.id,
+			candidate_id: CANDIDATE_3.id,
+			amount: rewards,
+		}));
+
+		assert_eq!(CandidatePool::<Test>::get(CANDIDATE_3.id).unwrap().bond, 100 + rewards);
+		assert_eq!(Balances::free_balance(CANDIDATE_3.id), balance_before);
+	});
+}
+
+#[test]
+fn should_ok_stake_delegator_rewards_into_largest_delegation() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_2.id, 100));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		assert_ok!(Dpos::set_reward_destination(ros(ACCOUNT_6.id), RewardDestination::Staked));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+
+		let rewards = Dpos::reward_points(ACCOUNT_6.id);
+		assert!(rewards > 0);
+		let balance_before = Balances::free_balance(ACCOUNT_6.id);
+		assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardStaked {
+			who: ACCOUNT_6.id,
+			candidate_id: CANDIDATE_3.id,
+			amount: rewards,
+		}));
+
+		assert_eq!(
+			DelegationInfos::<Test>::get(ACCOUNT_6.id, CANDIDATE_3.id).unwrap().amount,
+			250 + rewards
+		);
+		assert_eq!(DelegationInfos::<Test>::get(ACCOUNT_6.id, CANDIDATE_2.id).unwrap().amount, 100);
+		assert_eq!(
+			CandidatePool::<Test>::get(CANDIDATE_3.id).unwrap().total_delegations,
+			250 + rewards
+		);
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), balance_before);
+	});
+}
+
+#[test]
+fn should_ok_keep_rewards_free_when_nothing_to_stake() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		assert_ok!(Dpos::set_reward_destination(ros(ACCOUNT_6.id), RewardDestination::Staked));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+
+		// The delegation is removed while its rewards are still unclaimed
+		assert_ok!(Dpos::force_undelegate_candidate(
+			RuntimeOrigin::root(),
+			ACCOUNT_6.id,
+			CANDIDATE_3.id,
+			250
+		));
+		let rewards = Dpos::reward_points(ACCOUNT_6.id);
+		assert!(rewards > 0);
+		let balance_before = Balances::free_balance(ACCOUNT_6.id);
+		assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardClaimed {
+			claimer: ACCOUNT_6.id,
+			total_reward: rewards,
+		}));
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), balance_before + rewards);
+	});
+}
+
+#[test]
+fn should_ok_pay_leaving_candidate_rewards_into_free_balance() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_reward_destination(ros(CANDIDATE_1.id), RewardDestination::Staked));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+
+		// The rewards are not bonded again by the candidate leaving the pool
+		assert_ok!(Dpos::force_deregister_candidate(RuntimeOrigin::root(), CANDIDATE_1.id));
+		assert!(!Dpos::is_candidate(&CANDIDATE_1.id));
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+		assert_eq!(Balances::free_balance(CANDIDATE_1.id), CANDIDATE_1.balance + rewards);
+	});
+}
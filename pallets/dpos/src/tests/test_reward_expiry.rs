@@ -0,0 +1,300 @@
+use crate::{
+	mock::*,
+	types::{ExpiredRewardDestination, RewardSource},
+	weights::WeightInfo,
+	*,
+};
+use constants::{
+	AccountId, ACCOUNT_1, ACCOUNT_2, ACCOUNT_3, ACCOUNT_5, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2,
+	CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{assert_ok, traits::Hooks, weights::Weight};
+use tests::ros;
+
+const EXPIRY_EPOCHS: u32 = 3;
+
+// Leaves unclaimed rewards to a dormant account, earned in the current epoch
+fn give_dormant_rewards(who: AccountId, rewards: u128) {
+	RewardPoints::<Test>::insert(who, rewards);
+	RewardLastEarned::<Test>::insert(who, EpochIndex::<Test>::get());
+}
+
+fn expired_accounts() -> Vec<AccountId> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Dpos(Event::RewardExpired { who, .. }) => Some(who),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn should_ok_track_last_earned_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		assert!(Dpos::reward_points(CANDIDATE_1.id) > 0);
+		assert_eq!(Dpos::reward_last_earned(CANDIDATE_1.id), Some(0));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+		assert_eq!(Dpos::reward_last_earned(CANDIDATE_1.id), Some(1));
+
+		// The tracking is cleared with the rewards claimed
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_1.id)));
+		assert_eq!(Dpos::reward_last_earned(CANDIDATE_1.id), None);
+	});
+}
+
+#[test]
+fn should_ok_burn_expired_rewards() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		give_dormant_rewards(ACCOUNT_6.id, 500);
+
+		// The rewards are kept until they expire
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * (EXPIRY_EPOCHS as u64 - 1));
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 500);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+		let total_issuance = Balances::total_issuance();
+		let validator_rewards = Dpos::reward_points(CANDIDATE_1.id);
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardExpired {
+			who: ACCOUNT_6.id,
+			amount: 500,
+			destination: ExpiredRewardDestination::Burn,
+		}));
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+		assert_eq!(Dpos::reward_last_earned(ACCOUNT_6.id), None);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+
+		// The rewards of the validators earning every epoch do not expire
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), validator_rewards);
+		assert_eq!(expired_accounts(), vec![ACCOUNT_6.id]);
+	});
+}
+
+#[test]
+fn should_ok_forfeit_expired_rewards_to_treasury() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Treasury(ACCOUNT_5.id))
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		give_dormant_rewards(ACCOUNT_6.id, 500);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardExpired {
+			who: ACCOUNT_6.id,
+			amount: 500,
+			destination: ExpiredRewardDestination::Treasury(ACCOUNT_5.id),
+		}));
+		assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance + 500);
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), ACCOUNT_6.balance);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_bound_expiry_sweep_by_weight() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		let dormant_accounts = [ACCOUNT_1.id, ACCOUNT_2.id, ACCOUNT_3.id, ACCOUNT_6.id];
+		for who in dormant_accounts {
+			give_dormant_rewards(who, 500);
+		}
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+		// The dormant accounts and the validators are checked
+		assert_eq!(RewardLastEarned::<Test>::iter().count(), 7);
+
+		// Not enough weight to check any account
+		let base_weight = <Test as Config>::WeightInfo::sweep_expired_rewards(0);
+		assert_eq!(Dpos::on_idle(System::block_number(), base_weight), Weight::zero());
+		assert!(expired_accounts().is_empty());
+
+		// The sweep resumes after the last account checked
+		let sweep_weight = <Test as Config>::WeightInfo::sweep_expired_rewards(2);
+		for _ in 0..4 {
+			assert!(Dpos::on_idle(System::block_number(), sweep_weight).all_lte(sweep_weight));
+		}
+		let mut expired = expired_accounts();
+		expired.sort();
+		assert_eq!(expired, dormant_accounts.to_vec());
+		assert_eq!(RewardLastEarned::<Test>::iter().count(), 3);
+	});
+}
+
+#[test]
+fn should_ok_keep_rewards_without_expiry() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.reward_expiry(0, ExpiredRewardDestination::Burn)
+	.build()
+	.execute_with(|| {
+		give_dormant_rewards(ACCOUNT_6.id, 500);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+
+		assert_eq!(Dpos::on_idle(System::block_number(), Weight::MAX), Weight::zero());
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 500);
+	});
+}
+
+#[test]
+fn should_ok_burn_expired_rewards_from_reward_pot() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.reward_source(RewardSource::Pot)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::top_up_reward_pot(ros(ACCOUNT_6.id), 1_000));
+		give_dormant_rewards(ACCOUNT_3.id, 500);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+
+		let total_issuance = Balances::total_issuance();
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::RewardExpired {
+			who: ACCOUNT_3.id,
+			amount: 500,
+			destination: ExpiredRewardDestination::Burn,
+		}));
+		assert_eq!(Balances::free_balance(Dpos::reward_pot_account()), 500);
+		assert_eq!(Balances::total_issuance(), total_issuance - 500);
+	});
+}
+
+#[test]
+fn should_ok_burn_expired_rewards_from_treasury_source() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.reward_source(RewardSource::Treasury(ACCOUNT_5.id))
+	.build()
+	.execute_with(|| {
+		give_dormant_rewards(ACCOUNT_6.id, 200);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+
+		let total_issuance = Balances::total_issuance();
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::RewardExpired {
+			who: ACCOUNT_6.id,
+			amount: 200,
+			destination: ExpiredRewardDestination::Burn,
+		}));
+		assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance - 200);
+		assert_eq!(Balances::total_issuance(), total_issuance - 200);
+	});
+}
+
+#[test]
+fn should_ok_report_expired_rewards_not_paid_to_treasury() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Treasury(ACCOUNT_5.id))
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.reward_source(RewardSource::Pot)
+	.build()
+	.execute_with(|| {
+		give_dormant_rewards(ACCOUNT_6.id, 500);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * EXPIRY_EPOCHS as u64);
+
+		// The reward pot is empty, the treasury is not paid and nothing is burnt
+		let total_issuance = Balances::total_issuance();
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::RewardExpiryFailed {
+			who: ACCOUNT_6.id,
+			amount: 500,
+			error: Error::<Test>::InsufficientRewardSource.into(),
+		}));
+		assert!(!expired_accounts().contains(&ACCOUNT_6.id));
+		assert_eq!(Balances::free_balance(ACCOUNT_5.id), ACCOUNT_5.balance);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_expire_settled_rewards_from_earned_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_expiry(EXPIRY_EPOCHS, ExpiredRewardDestination::Burn)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(1)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 1);
+		// The delegator only earns rewards in the epoch 1
+		assert_ok!(Dpos::toggle_candidate_status(&CANDIDATE_3.id));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert!(Dpos::reward_points(ACCOUNT_6.id) > 0);
+		assert_eq!(Dpos::reward_last_earned(ACCOUNT_6.id), None);
+
+		// The rewards of the epoch 1 are settled once the epoch is pruned, as earned in the
+		// epoch 1
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(Dpos::reward_last_earned(ACCOUNT_6.id), Some(1));
+		assert!(!expired_accounts().contains(&ACCOUNT_6.id));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * (1 + EXPIRY_EPOCHS as u64));
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		assert!(expired_accounts().contains(&ACCOUNT_6.id));
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+	});
+}
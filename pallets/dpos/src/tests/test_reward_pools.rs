@@ -0,0 +1,238 @@
+use crate::{mock::*, weights::WeightInfo, *};
+use constants::{
+	ACCOUNT_3, ACCOUNT_4, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::{
+	frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight},
+	sp_runtime::{traits::Zero, FixedU128},
+};
+use tests::ros;
+
+#[test]
+fn should_ok_create_reward_pools_from_epoch_snapshot() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+
+		let reward_pool = Dpos::reward_pool(1, CANDIDATE_3.id).unwrap();
+		assert_eq!(reward_pool.bond, 100);
+		assert_eq!(reward_pool.total_delegations, 250);
+		assert_eq!(Dpos::reward_pool(1, CANDIDATE_1.id).unwrap().total_delegations, 0);
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(1).count(), 3);
+	});
+}
+
+#[test]
+fn should_ok_accrue_rewards_per_stake_without_settling_delegators() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+		let reward_pool = Dpos::reward_pool(1, CANDIDATE_3.id).unwrap();
+		assert!(reward_pool.reward_per_stake > FixedU128::zero());
+
+		// The delegator rewards are only tracked by the reward pool until they are claimed
+		let rewards = reward_pool.rewards_of(250, FixedU128::zero());
+		assert!(rewards > 0);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), rewards);
+		assert_eq!(RewardPoints::<Test>::get(ACCOUNT_6.id), 0);
+		assert_eq!(ClaimedRewardPerStake::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn should_ok_claim_delegator_rewards_once() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+
+		let rewards = Dpos::reward_points(ACCOUNT_6.id);
+		let balance_before = Balances::free_balance(ACCOUNT_6.id);
+		assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), balance_before + rewards);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardClaimed {
+			claimer: ACCOUNT_6.id,
+			total_reward: rewards,
+		}));
+
+		// The rewards claimed are not paid out again in the same epoch
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+		assert_noop!(Dpos::claim_reward(ros(ACCOUNT_6.id)), Error::<Test>::NoClaimableRewardFound);
+
+		// Only the rewards accrued after the claim are claimable
+		let reward_per_stake = Dpos::reward_pool(1, CANDIDATE_3.id).unwrap().reward_per_stake;
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 6);
+		let reward_pool = Dpos::reward_pool(1, CANDIDATE_3.id).unwrap();
+		assert!(reward_pool.reward_per_stake > reward_per_stake);
+		assert_eq!(
+			Dpos::reward_points(ACCOUNT_6.id),
+			reward_pool.rewards_of(250, reward_per_stake)
+		);
+	});
+}
+
+#[test]
+fn should_ok_settle_delegator_rewards_of_pruned_epochs() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(1)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		let rewards = Dpos::reward_points(ACCOUNT_6.id);
+		assert!(rewards > 0);
+
+		// The epoch 1 is pruned, its delegations and reward pools are kept until settled
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(1).count(), 3);
+		assert_eq!(EpochDelegations::<Test>::get((1, ACCOUNT_6.id, CANDIDATE_3.id)), Some(250));
+		assert_eq!(RewardPoints::<Test>::get(ACCOUNT_6.id), 0);
+
+		Dpos::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(OldestUnsettledEpoch::<Test>::get(), 2);
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((1,)).count(), 0);
+		assert!(RewardPoints::<Test>::get(ACCOUNT_6.id) >= rewards);
+		// The expiry of the rewards starts from the epoch they were earned in
+		assert_eq!(RewardLastEarned::<Test>::get(ACCOUNT_6.id), Some(1));
+
+		assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
+	});
+}
+
+#[test]
+fn should_ok_bound_pruned_settlement_by_weight() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.history_depth(1)
+	.build()
+	.execute_with(|| {
+		let delegators = [ACCOUNT_3.id, ACCOUNT_4.id, ACCOUNT_6.id];
+		for delegator in delegators {
+			assert_ok!(Dpos::delegate_candidate(ros(delegator), CANDIDATE_3.id, 100));
+		}
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((1,)).count(), 3);
+
+		// The genesis epoch has no delegations, its reward pools are closed first
+		Dpos::settle_pruned_rewards(<Test as Config>::WeightInfo::settle_pruned_rewards(
+			1,
+			MaxActiveValidators::get(),
+		));
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(OldestUnsettledEpoch::<Test>::get(), 1);
+
+		// Not enough weight to settle a delegation and close the reward pools
+		let close_weight =
+			<Test as Config>::WeightInfo::settle_pruned_rewards(0, MaxActiveValidators::get());
+		assert_eq!(
+			Dpos::settle_pruned_rewards(close_weight),
+			<Test as Config>::WeightInfo::settle_pruned_rewards(0, 0)
+		);
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((1,)).count(), 3);
+
+		// The settlement resumes in the next blocks and the pools are closed once all the
+		// delegations are settled
+		let page_weight =
+			<Test as Config>::WeightInfo::settle_pruned_rewards(2, MaxActiveValidators::get());
+		assert!(Dpos::settle_pruned_rewards(page_weight).all_lte(page_weight));
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((1,)).count(), 1);
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(1).count(), 3);
+		assert_eq!(OldestUnsettledEpoch::<Test>::get(), 1);
+
+		assert!(Dpos::settle_pruned_rewards(page_weight).all_lte(page_weight));
+		assert_eq!(EpochDelegations::<Test>::iter_prefix((1,)).count(), 0);
+		assert_eq!(ValidatorRewardPools::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(OldestUnsettledEpoch::<Test>::get(), 2);
+		for delegator in delegators {
+			assert!(RewardPoints::<Test>::get(delegator) > 0);
+		}
+
+		// Nothing is left to settle
+		assert_eq!(Dpos::settle_pruned_rewards(Weight::MAX), Weight::zero());
+	});
+}
+
+#[test]
+fn should_ok_refund_claim_reward_weight_of_unread_delegations() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+
+		let post_info = Dpos::claim_reward(ros(ACCOUNT_6.id)).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<Test as Config>::WeightInfo::claim_reward(1)));
+	});
+}
+
+#[test]
+fn should_ok_settle_reward_pools_replaced_by_emergency_validators() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 3);
+		let rewards = Dpos::reward_points(ACCOUNT_6.id);
+		assert!(rewards > 0);
+
+		assert_ok!(Dpos::force_set_emergency_validators(
+			RuntimeOrigin::root(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id]
+		));
+		assert_eq!(Dpos::reward_pool(1, CANDIDATE_3.id), None);
+		assert_eq!(RewardPoints::<Test>::get(ACCOUNT_6.id), rewards);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), rewards);
+	});
+}
@@ -0,0 +1,150 @@
+use crate::{mock::*, types::RewardSource, *};
+use constants::{ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use tests::ros;
+
+#[test]
+fn should_ok_top_up_reward_pot() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Pot)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::top_up_reward_pot(ros(ACCOUNT_6.id), 1_000));
+		assert_eq!(Balances::free_balance(Dpos::reward_pot_account()), 1_000);
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), ACCOUNT_6.balance - 1_000);
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardPotToppedUp {
+			who: ACCOUNT_6.id,
+			amount: 1_000,
+		}));
+	});
+}
+
+#[test]
+fn should_failed_top_up_reward_pot_with_zero_amount() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Pot)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::top_up_reward_pot(ros(ACCOUNT_6.id), 0),
+			Error::<Test>::InvalidZeroAmount
+		);
+	});
+}
+
+#[test]
+fn should_ok_claim_reward_from_reward_pot() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Pot)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::top_up_reward_pot(ros(ACCOUNT_6.id), 1_000));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+		let total_issuance = Balances::total_issuance();
+		let balance_before = Balances::free_balance(CANDIDATE_1.id);
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_1.id)));
+
+		// The rewards are transferred from the reward pot instead of being minted
+		assert_eq!(Balances::free_balance(CANDIDATE_1.id), balance_before + rewards);
+		assert_eq!(Balances::free_balance(Dpos::reward_pot_account()), 1_000 - rewards);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
+	});
+}
+
+#[test]
+fn should_failed_claim_reward_with_insufficient_reward_pot() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Pot)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+
+		// The reward points are kept when the reward pot is insufficient
+		assert_noop!(
+			Dpos::claim_reward(ros(CANDIDATE_1.id)),
+			Error::<Test>::InsufficientRewardSource
+		);
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), rewards);
+
+		assert_ok!(Dpos::top_up_reward_pot(ros(ACCOUNT_6.id), 1_000));
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_1.id)));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::RewardClaimed {
+			claimer: CANDIDATE_1.id,
+			total_reward: rewards,
+		}));
+	});
+}
+
+#[test]
+fn should_ok_claim_reward_from_treasury() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Treasury(ACCOUNT_6.id))
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		let balance_before = Balances::free_balance(CANDIDATE_1.id);
+		assert_ok!(Dpos::claim_reward(ros(CANDIDATE_1.id)));
+		assert_eq!(Balances::free_balance(CANDIDATE_1.id), balance_before + rewards);
+		assert_eq!(Balances::free_balance(ACCOUNT_6.id), ACCOUNT_6.balance - rewards);
+	});
+}
+
+#[test]
+fn should_ok_keep_reward_points_of_leaving_candidate_with_insufficient_source() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.reward_source(RewardSource::Pot)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		let rewards = Dpos::reward_points(CANDIDATE_1.id);
+		assert!(rewards > 0);
+
+		assert_ok!(Dpos::force_deregister_candidate(RuntimeOrigin::root(), CANDIDATE_1.id));
+		assert!(!Dpos::is_candidate(&CANDIDATE_1.id));
+		assert_eq!(Dpos::reward_points(CANDIDATE_1.id), rewards);
+	});
+}
@@ -0,0 +1,300 @@
+use crate::{
+	mock::*,
+	types::{EpochTrigger, Forcing},
+	*,
+};
+use constants::{
+	AccountId, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use pallet_session::SessionManager;
+use tests::test_helpers;
+
+/// Calls the session manager in the same order as `pallet_session` at genesis
+fn start_genesis_session() -> (Option<Vec<AccountId>>, Option<Vec<AccountId>>) {
+	let initial_validators_0 = Dpos::new_session_genesis(0);
+	let initial_validators_1 = Dpos::new_session_genesis(1);
+	Dpos::start_session(0);
+	(initial_validators_0, initial_validators_1)
+}
+
+/// Calls the session manager in the same order as `pallet_session` when rotating the session
+fn rotate_session(start_index: u32) -> Option<Vec<AccountId>> {
+	Dpos::end_session(start_index - 1);
+	Dpos::start_session(start_index);
+	Dpos::new_session(start_index + 1)
+}
+
+#[test]
+fn should_ok_move_epoch_with_sessions() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session)
+	.build()
+	.execute_with(|| {
+		let genesis_validators = vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id];
+		let (initial_validators_0, initial_validators_1) = start_genesis_session();
+		assert_eq!(initial_validators_0, Some(genesis_validators.clone()));
+		assert_eq!(initial_validators_1, Some(genesis_validators.clone()));
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+
+		// The epoch does not move with the block number
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 400);
+
+		// The new candidate is elected when the session is planned, the planned set is installed
+		// one session later
+		let planned_validators = rotate_session(1).unwrap();
+		assert_eq!(planned_validators, vec![CANDIDATE_4.id, CANDIDATE_1.id, CANDIDATE_2.id]);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(test_helpers::active_validator_ids(), genesis_validators);
+
+		rotate_session(2);
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_4.id]
+		);
+		assert_eq!(
+			Dpos::last_epoch_snapshot()
+				.unwrap()
+				.validators
+				.keys()
+				.cloned()
+				.collect::<Vec<_>>(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_4.id]
+		);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+			last_epoch: 1,
+			next_epoch: 2,
+			at_block: TEST_BLOCKS_PER_EPOCH,
+			total_candidates: 4,
+			total_validators: 3,
+			replaced_validators: 1,
+			deferred_challengers: 0,
+			rotated_in: vec![CANDIDATE_4.id],
+			rotated_out: vec![CANDIDATE_3.id],
+		}));
+	});
+}
+
+#[test]
+fn should_ok_keep_validator_set_when_session_election_fails() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session)
+	.min_active_validators(3)
+	.build()
+	.execute_with(|| {
+		start_genesis_session();
+		let previous_active_set = Dpos::active_validators();
+
+		assert_ok!(Dpos::toggle_candidate_status(&CANDIDATE_3.id));
+		assert_eq!(rotate_session(1), None);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+
+		rotate_session(2);
+		assert_eq!(Dpos::active_validators(), previous_active_set);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::ElectionFailed {
+			epoch: 2,
+			online_candidates: 2,
+			retained_validators: 3,
+		}));
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn should_ok_plan_emergency_validator_set_into_next_session() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+		(CANDIDATE_4.id, 50),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session)
+	.build()
+	.execute_with(|| {
+		let elected_validators = vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id];
+		start_genesis_session();
+		assert_eq!(rotate_session(1), Some(elected_validators.clone()));
+
+		// The emergency set replaces the elected set planned already
+		let emergency_validators = vec![CANDIDATE_2.id, CANDIDATE_3.id, CANDIDATE_4.id];
+		assert_ok!(Dpos::force_set_emergency_validators(
+			RuntimeOrigin::root(),
+			emergency_validators.clone()
+		));
+		assert_eq!(
+			PendingValidatorSet::<Test>::get()
+				.unwrap()
+				.into_iter()
+				.map(|(validator, _, _)| validator)
+				.collect::<Vec<_>>(),
+			emergency_validators
+		);
+
+		// The emergency set is kept and planned into the next session instead of an elected set
+		assert_eq!(rotate_session(2), Some(emergency_validators.clone()));
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+		assert_eq!(test_helpers::active_validator_ids(), emergency_validators);
+
+		// The session of the emergency set starts, then the sessions are elected again
+		assert_eq!(rotate_session(3), Some(elected_validators.clone()));
+		assert_eq!(EpochIndex::<Test>::get(), 3);
+		assert_eq!(test_helpers::active_validator_ids(), emergency_validators);
+
+		rotate_session(4);
+		assert_eq!(test_helpers::active_validator_ids(), elected_validators);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Dpos(Event::ElectionFailed { .. })
+		)));
+	});
+}
+
+#[test]
+fn should_failed_force_emergency_validators_without_session_keys() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session)
+	.build()
+	.execute_with(|| {
+		start_genesis_session();
+
+		AccountsWithoutSessionKeys::set(vec![CANDIDATE_3.id]);
+		assert_noop!(
+			Dpos::force_set_emergency_validators(
+				RuntimeOrigin::root(),
+				vec![CANDIDATE_1.id, CANDIDATE_3.id]
+			),
+			Error::<Test>::MissingSessionKeys
+		);
+		AccountsWithoutSessionKeys::set(vec![]);
+
+		assert!(!PendingEmergencySession::<Test>::get());
+	});
+}
+
+#[test]
+fn should_ok_ignore_sessions_in_block_number_mode() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::BlockNumber)
+	.build()
+	.execute_with(|| {
+		assert_eq!(start_genesis_session(), (None, None));
+		assert_eq!(rotate_session(1), None);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn should_ok_elect_candidates_with_session_keys_only() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session)
+	.build()
+	.execute_with(|| {
+		start_genesis_session();
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 400);
+
+		// The keys of the candidate are purged after its registration
+		AccountsWithoutSessionKeys::set(vec![CANDIDATE_4.id]);
+		assert_eq!(rotate_session(1), Some(vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]));
+
+		AccountsWithoutSessionKeys::set(vec![]);
+		assert_eq!(rotate_session(2), Some(vec![CANDIDATE_4.id, CANDIDATE_1.id, CANDIDATE_2.id]));
+	});
+}
+
+#[test]
+fn should_ok_elect_genesis_session_with_session_keys() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Session);
+	// The session keys are not registered yet when the genesis config of the pallet is built
+	ext.accounts_without_session_keys(vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Dpos::active_validators().to_vec(), vec![]);
+
+			AccountsWithoutSessionKeys::set(vec![CANDIDATE_3.id]);
+			let (initial_validators_0, initial_validators_1) = start_genesis_session();
+			assert_eq!(initial_validators_0, Some(vec![CANDIDATE_1.id, CANDIDATE_2.id]));
+			assert_eq!(initial_validators_1, Some(vec![CANDIDATE_1.id, CANDIDATE_2.id]));
+			assert_eq!(test_helpers::active_validator_ids(), vec![CANDIDATE_1.id, CANDIDATE_2.id]);
+			assert!(Dpos::last_epoch_snapshot().unwrap().validators.contains_key(&CANDIDATE_1.id));
+			assert_eq!(EpochIndex::<Test>::get(), 0);
+		});
+}
+
+#[test]
+fn should_failed_force_epochs_moved_by_session() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_trigger(EpochTrigger::Session)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::force_new_epoch(RuntimeOrigin::root()),
+			Error::<Test>::EpochForcingNotSupported
+		);
+		assert_noop!(
+			Dpos::force_no_epochs(RuntimeOrigin::root()),
+			Error::<Test>::EpochForcingNotSupported
+		);
+		assert_noop!(
+			Dpos::force_new_epoch_always(RuntimeOrigin::root()),
+			Error::<Test>::EpochForcingNotSupported
+		);
+		assert_eq!(Dpos::force_epoch(), Forcing::NotForcing);
+	});
+}
@@ -0,0 +1,227 @@
+use crate::{mock::*, *};
+use constants::{ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::{
+	frame_support::{assert_noop, assert_ok},
+	sp_runtime::{DispatchError, Perbill},
+};
+use tests::ros;
+
+#[test]
+fn should_ok_set_commission_from_next_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(10)));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::CommissionSet {
+			candidate_id: CANDIDATE_3.id,
+			commission: Perbill::from_percent(10),
+			effective_from_epoch: 1,
+		}));
+		let candidate_detail = CandidatePool::<Test>::get(CANDIDATE_3.id).unwrap();
+		assert_eq!(candidate_detail.commission, Perbill::from_percent(10));
+		assert_eq!(candidate_detail.commission_updated_at, Some(0));
+
+		// The commission of the current epoch is kept
+		assert_eq!(Dpos::reward_pool(0, CANDIDATE_3.id).unwrap().commission, Perbill::zero());
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			Dpos::reward_pool(1, CANDIDATE_3.id).unwrap().commission,
+			Perbill::from_percent(10)
+		);
+	});
+}
+
+#[test]
+fn should_failed_set_commission_twice_in_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(10)));
+		assert_noop!(
+			Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(20)),
+			Error::<Test>::CommissionUpdateTooSoon
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(20)));
+	});
+}
+
+#[test]
+fn should_failed_set_commission_out_of_bounds() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission_bounds(
+			RuntimeOrigin::root(),
+			Perbill::from_percent(5),
+			Perbill::from_percent(50)
+		));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::CommissionBoundsSet {
+			min_commission: Perbill::from_percent(5),
+			max_commission: Perbill::from_percent(50),
+		}));
+
+		assert_noop!(
+			Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(1)),
+			Error::<Test>::CommissionOutOfBounds
+		);
+		assert_noop!(
+			Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(60)),
+			Error::<Test>::CommissionOutOfBounds
+		);
+		assert_noop!(
+			Dpos::set_commission(ros(ACCOUNT_6.id), Perbill::from_percent(10)),
+			Error::<Test>::CandidateDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn should_failed_set_invalid_commission_bounds() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::set_commission_bounds(
+				ros(CANDIDATE_1.id),
+				Perbill::zero(),
+				Perbill::from_percent(50)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Dpos::set_commission_bounds(
+				RuntimeOrigin::root(),
+				Perbill::from_percent(50),
+				Perbill::from_percent(5)
+			),
+			Error::<Test>::InvalidCommissionBounds
+		);
+	});
+}
+
+#[test]
+fn should_ok_clamp_commission_into_bounds() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(80)));
+		assert_ok!(Dpos::set_commission_bounds(
+			RuntimeOrigin::root(),
+			Perbill::from_percent(5),
+			Perbill::from_percent(50)
+		));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			Dpos::reward_pool(1, CANDIDATE_1.id).unwrap().commission,
+			Perbill::from_percent(5)
+		);
+		assert_eq!(
+			Dpos::reward_pool(1, CANDIDATE_3.id).unwrap().commission,
+			Perbill::from_percent(50)
+		);
+	});
+}
+
+#[test]
+fn should_ok_payout_epoch_with_commission() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_points_rewards(1_000)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(10)));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+
+		let epoch_points = Dpos::epoch_reward_points(1);
+		let total_reward =
+			Perbill::from_rational(epoch_points.points_of(&CANDIDATE_3.id), epoch_points.total) *
+				1_000;
+		// The validator takes its commission, the rest is shared pro-rata to the stake
+		let shared_reward = total_reward - Perbill::from_percent(10) * total_reward;
+		let delegator_rewards = Perbill::from_rational(250u128, 350) * shared_reward;
+
+		assert_ok!(Dpos::payout_epoch(ros(ACCOUNT_6.id), CANDIDATE_3.id, 1));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::EpochPaidOut {
+			epoch: 1,
+			validator: CANDIDATE_3.id,
+			validator_reward: total_reward - delegator_rewards,
+			delegator_rewards,
+		}));
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), delegator_rewards);
+	});
+}
+
+#[test]
+fn should_ok_reward_block_with_commission() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.balance_rate(200)
+	.find_author_disabled()
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_commission(ros(CANDIDATE_3.id), Perbill::from_percent(10)));
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_3.id, 250));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		Dpos::reward_block_author(&CANDIDATE_3.id);
+		let block_reward = Dpos::block_reward(350);
+		assert_eq!(block_reward, 70);
+		// The validator takes its commission, the rest is shared pro-rata to the stake
+		let shared_reward = block_reward - Perbill::from_percent(10) * block_reward;
+		let delegator_rewards = Perbill::from_rational(250u128, 350) * shared_reward;
+		assert_eq!(delegator_rewards, 45);
+		assert_eq!(Dpos::reward_points(ACCOUNT_6.id), delegator_rewards);
+		assert_eq!(Dpos::reward_points(CANDIDATE_3.id), block_reward - delegator_rewards);
+		assert_eq!(Dpos::current_epoch_info().unwrap().total_rewards, block_reward);
+	});
+}
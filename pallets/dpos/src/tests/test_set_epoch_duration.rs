@@ -0,0 +1,108 @@
+use crate::{mock::*, *};
+use constants::{ACCOUNT_1, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH};
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use tests::ros;
+
+#[test]
+fn should_failed_invalid_origin() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(Dpos::set_epoch_duration(ros(ACCOUNT_1.id), 10), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn should_failed_zero_epoch_duration() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_noop!(
+			Dpos::set_epoch_duration(RuntimeOrigin::root(), 0),
+			Error::<Test>::InvalidEpochDuration
+		);
+	});
+}
+
+#[test]
+fn should_ok_apply_epoch_duration_from_next_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.build()
+	.execute_with(|| {
+		assert_eq!(Dpos::next_epoch_start_block(), TEST_BLOCKS_PER_EPOCH);
+
+		ext.run_to_block(3);
+		assert_ok!(Dpos::set_epoch_duration(RuntimeOrigin::root(), 5));
+		System::assert_last_event(RuntimeEvent::Dpos(Event::EpochDurationSet {
+			epoch_duration: 5,
+			effective_from_epoch: 1,
+		}));
+		assert_eq!(Dpos::epoch_duration(), 5);
+
+		// The current epoch keeps its planned end
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH - 1);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(Dpos::next_epoch_start_block(), TEST_BLOCKS_PER_EPOCH + 5);
+		assert_eq!(
+			Dpos::current_epoch_info().unwrap().planned_end_block,
+			TEST_BLOCKS_PER_EPOCH + 5
+		);
+
+		// The following epochs move with the new duration
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 4);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 5);
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 10);
+		assert_eq!(EpochIndex::<Test>::get(), 3);
+	});
+}
+
+#[test]
+fn should_ok_start_paged_election_before_planned_epoch_end() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_lookahead(2)
+	.build()
+	.execute_with(|| {
+		assert_ok!(Dpos::set_epoch_duration(RuntimeOrigin::root(), 7));
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::next_epoch_start_block(), TEST_BLOCKS_PER_EPOCH + 7);
+
+		// The election starts ahead of the planned end rather than a multiple of the duration
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 4);
+		assert_eq!(PagedElectionPhase::<Test>::get(), None);
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 5);
+		assert!(PagedElectionPhase::<Test>::get().is_some());
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH + 7);
+		assert_eq!(EpochIndex::<Test>::get(), 2);
+		assert_eq!(PendingValidatorSet::<Test>::get(), None);
+	});
+}
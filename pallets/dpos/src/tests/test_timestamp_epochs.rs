@@ -0,0 +1,150 @@
+use crate::{mock::*, *};
+use constants::{CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, TEST_BLOCKS_PER_EPOCH, TEST_BLOCK_TIME};
+use frame::deps::frame_support::{assert_noop, assert_ok};
+use tests::ros;
+use types::{DelayActionRequest, DelayActionType, EpochTrigger, Forcing};
+
+const GENESIS_TIME: u64 = 1_700_000_000_000;
+const EPOCH_TIME: u64 = TEST_BLOCKS_PER_EPOCH * TEST_BLOCK_TIME;
+
+/// Produces the next block. The timestamp of the block is set after `on_initialize`, as done by
+/// the timestamp inherent
+fn produce_block(ext: &TestExtBuilder, block_time: u64) {
+	let now = Timestamp::now();
+	ext.next_block();
+	Timestamp::set_timestamp(now + block_time);
+}
+
+#[test]
+fn should_ok_plan_genesis_epoch_with_first_timestamp() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Timestamp)
+	.build()
+	.execute_with(|| {
+		// No timestamp is set yet, the epochs don't move with the block number
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(EpochIndex::<Test>::get(), 0);
+		assert_eq!(Dpos::next_epoch_start_time(), None);
+
+		Timestamp::set_timestamp(GENESIS_TIME);
+		produce_block(&ext, TEST_BLOCK_TIME);
+		assert_eq!(Dpos::next_epoch_start_time(), Some(GENESIS_TIME + EPOCH_TIME));
+	});
+}
+
+#[test]
+fn should_ok_move_epoch_when_epoch_time_elapsed() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Timestamp)
+	.build()
+	.execute_with(|| {
+		Timestamp::set_timestamp(GENESIS_TIME);
+		produce_block(&ext, TEST_BLOCK_TIME);
+		assert_eq!(Dpos::next_epoch_start_time(), Some(GENESIS_TIME + EPOCH_TIME));
+
+		// The blocks are produced twice slower than expected, the epoch lasts the same time with
+		// fewer blocks
+		while Timestamp::now() < GENESIS_TIME + EPOCH_TIME {
+			produce_block(&ext, TEST_BLOCK_TIME * 2);
+			assert_eq!(EpochIndex::<Test>::get(), 0);
+		}
+		let last_timestamp = Timestamp::now();
+		produce_block(&ext, TEST_BLOCK_TIME * 2);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert!(System::block_number() < TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::next_epoch_start_time(), Some(last_timestamp + EPOCH_TIME));
+	});
+}
+
+#[test]
+fn should_ok_measure_delay_action_in_time() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Timestamp)
+	.build()
+	.execute_with(|| {
+		Timestamp::set_timestamp(GENESIS_TIME);
+		assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_3.id)));
+		assert_eq!(
+			DelayActionRequests::<Test>::get(CANDIDATE_3.id, DelayActionType::CandidateLeaved),
+			Some(DelayActionRequest {
+				amount: None,
+				created_at: 1,
+				delay_for: DelayDeregisterCandidateDuration::get(),
+				target: None,
+				created_at_time: Some(GENESIS_TIME),
+			})
+		);
+
+		// The delay has passed in blocks but not in time
+		for _ in 0..DelayDeregisterCandidateDuration::get() {
+			produce_block(&ext, TEST_BLOCK_TIME / 2);
+		}
+		assert_noop!(
+			Dpos::execute_deregister_candidate(ros(CANDIDATE_3.id)),
+			Error::<Test>::ActionIsStillInDelayDuration
+		);
+
+		Timestamp::set_timestamp(
+			GENESIS_TIME + Dpos::blocks_to_time(DelayDeregisterCandidateDuration::get()),
+		);
+		assert_ok!(Dpos::execute_deregister_candidate(ros(CANDIDATE_3.id)));
+		assert!(!Dpos::is_candidate(&CANDIDATE_3.id));
+	});
+}
+
+#[test]
+fn should_ok_force_epochs_moved_by_timestamp() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.epoch_trigger(EpochTrigger::Timestamp)
+	.build()
+	.execute_with(|| {
+		Timestamp::set_timestamp(GENESIS_TIME);
+		produce_block(&ext, TEST_BLOCK_TIME);
+
+		// The epoch moves at the next block although its time has not elapsed, the end of the
+		// new epoch is planned from its start
+		assert_ok!(Dpos::force_new_epoch(RuntimeOrigin::root()));
+		let forced_at = Timestamp::now();
+		produce_block(&ext, TEST_BLOCK_TIME);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+		assert_eq!(Dpos::force_epoch(), Forcing::NotForcing);
+		assert_eq!(Dpos::next_epoch_start_time(), Some(forced_at + EPOCH_TIME));
+
+		// The epoch does not move after its time elapsed while the epochs are frozen
+		assert_ok!(Dpos::force_no_epochs(RuntimeOrigin::root()));
+		while Timestamp::now() <= forced_at + EPOCH_TIME {
+			produce_block(&ext, TEST_BLOCK_TIME);
+		}
+		produce_block(&ext, TEST_BLOCK_TIME);
+		assert_eq!(EpochIndex::<Test>::get(), 1);
+
+		assert_ok!(Dpos::force_new_epoch_always(RuntimeOrigin::root()));
+		produce_block(&ext, TEST_BLOCK_TIME);
+		produce_block(&ext, TEST_BLOCK_TIME);
+		assert_eq!(EpochIndex::<Test>::get(), 3);
+	});
+}
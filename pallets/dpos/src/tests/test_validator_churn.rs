@@ -0,0 +1,135 @@
+use crate::{mock::*, *};
+use constants::{
+	ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, CANDIDATE_5, CANDIDATE_6,
+	TEST_BLOCKS_PER_EPOCH,
+};
+use frame::deps::frame_support::assert_ok;
+use tests::{ros, test_helpers};
+
+#[test]
+fn should_ok_limit_replaced_validators_per_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.max_validator_churn(1)
+	.build()
+	.execute_with(|| {
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+		);
+
+		// Three stronger challengers join the pool
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 400);
+		test_helpers::register_new_candidate(CANDIDATE_5.id, CANDIDATE_5.balance, 600);
+		test_helpers::register_new_candidate(CANDIDATE_6.id, CANDIDATE_6.balance, 500);
+
+		// Only the strongest challenger replaces the weakest validator
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_5.id]
+		);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+			last_epoch: 0,
+			next_epoch: 1,
+			at_block: TEST_BLOCKS_PER_EPOCH,
+			total_candidates: 6,
+			total_validators: 3,
+			replaced_validators: 1,
+			deferred_challengers: 2,
+			rotated_in: vec![CANDIDATE_5.id],
+			rotated_out: vec![CANDIDATE_3.id],
+		}));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_5.id, CANDIDATE_6.id]
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_4.id, CANDIDATE_5.id, CANDIDATE_6.id]
+		);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+			last_epoch: 2,
+			next_epoch: 3,
+			at_block: TEST_BLOCKS_PER_EPOCH * 3,
+			total_candidates: 6,
+			total_validators: 3,
+			replaced_validators: 1,
+			deferred_challengers: 0,
+			rotated_in: vec![CANDIDATE_4.id],
+			rotated_out: vec![CANDIDATE_1.id],
+		}));
+	});
+}
+
+#[test]
+fn should_ok_fill_slots_of_offline_validators_beyond_churn_limit() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.max_validator_churn(1)
+	.build()
+	.execute_with(|| {
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 400);
+		test_helpers::register_new_candidate(CANDIDATE_5.id, CANDIDATE_5.balance, 50);
+
+		// Two validators leave, there are no outgoing validators to carry over
+		assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_2.id)));
+		assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_3.id)));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_4.id, CANDIDATE_5.id]
+		);
+	});
+}
+
+#[test]
+fn should_ok_churn_limit_applies_to_delegated_challengers() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.max_validator_churn(0)
+	.build()
+	.execute_with(|| {
+		test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 50);
+		assert_ok!(Dpos::delegate_candidate(ros(ACCOUNT_6.id), CANDIDATE_4.id, 1000));
+
+		// Without any churn allowed, the current set carries over with the updated stakes
+		let active_validator_set = Dpos::select_active_validator_set();
+		assert_eq!(
+			active_validator_set,
+			vec![
+				(CANDIDATE_1.id, 300, 300),
+				(CANDIDATE_2.id, 200, 200),
+				(CANDIDATE_3.id, 100, 100)
+			]
+		);
+		assert_eq!(DeferredChallengers::<Test>::get(), 1);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(Dpos::active_validators().to_vec(), active_validator_set);
+		assert_eq!(DeferredChallengers::<Test>::get(), 0);
+	});
+}
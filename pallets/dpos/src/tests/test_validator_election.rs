@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use sp_runtime::BoundedBTreeMap;
+
 use crate::{mock::*, *};
 use constants::{
 	AccountId, ACCOUNT_6, CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, CANDIDATE_5,
@@ -39,7 +41,7 @@ fn should_ok_epoch_snapshot_is_correct() {
 	ext.epoch_duration(TEST_BLOCKS_PER_EPOCH).build().execute_with(|| {
 		let active_validator_set = Dpos::select_active_validator_set();
 		assert_eq!(
-			Some(Dpos::capture_epoch_snapshot(&active_validator_set)),
+			Some(Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &active_validator_set)),
 			Dpos::last_epoch_snapshot()
 		);
 	});
@@ -61,18 +63,19 @@ fn should_ok_epoch_snapshot_update_with_new_candidates() {
 			ext.run_to_block(2);
 			// Attemp to register as candidate without enough fund in the account
 			assert_eq!(
-				Dpos::capture_epoch_snapshot(&Dpos::active_validators()),
+				Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &Dpos::active_validators()),
 				EpochSnapshot {
-					validators: BTreeMap::from_iter(vec![
+					validators: BoundedBTreeMap::try_from(BTreeMap::from_iter(vec![
 						(CANDIDATE_1.id, 300),
 						(CANDIDATE_2.id, 300),
 						(CANDIDATE_3.id, 300),
-					]),
-					delegations: BTreeMap::default()
+					]))
+					.unwrap(),
+					total_delegations: BoundedBTreeMap::new()
 				}
 			);
 			assert_eq!(
-				Dpos::capture_epoch_snapshot(&Dpos::active_validators())
+				Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &Dpos::active_validators())
 					.validators
 					.get(&CANDIDATE_4.id),
 				None
@@ -82,7 +85,7 @@ fn should_ok_epoch_snapshot_update_with_new_candidates() {
 			ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
 
 			assert_eq!(
-				Dpos::capture_epoch_snapshot(&Dpos::active_validators())
+				Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &Dpos::active_validators())
 					.validators
 					.get(&CANDIDATE_4.id),
 				Some(&500)
@@ -91,7 +94,7 @@ fn should_ok_epoch_snapshot_update_with_new_candidates() {
 			test_helpers::register_new_candidate(CANDIDATE_5.id, CANDIDATE_5.balance, 600);
 
 			assert_eq!(
-				Dpos::capture_epoch_snapshot(&Dpos::active_validators())
+				Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &Dpos::active_validators())
 					.validators
 					.get(&CANDIDATE_5.id),
 				None
@@ -100,7 +103,7 @@ fn should_ok_epoch_snapshot_update_with_new_candidates() {
 			ext.run_to_block_from(TEST_BLOCKS_PER_EPOCH, TEST_BLOCKS_PER_EPOCH);
 
 			assert_eq!(
-				Dpos::capture_epoch_snapshot(&Dpos::active_validators())
+				Dpos::capture_epoch_snapshot(EpochIndex::<Test>::get(), &Dpos::active_validators())
 					.validators
 					.get(&CANDIDATE_5.id),
 				Some(&600)
@@ -120,7 +123,7 @@ fn should_ok_reward_distributed_for_validators() {
 		.build()
 		.execute_with(|| {
 			let active_validator_set = Dpos::select_active_validator_set();
-			assert!(Dpos::active_validators().len() > 0);
+			assert!(!Dpos::active_validators().is_empty());
 			assert_eq!(Dpos::active_validators(), active_validator_set);
 			assert_eq!(
 				Dpos::last_epoch_snapshot(),
@@ -139,21 +142,21 @@ fn should_ok_reward_distributed_for_validators() {
 				{
 					if Some(*active_validator) == maybe_author {
 						assert_eq!(
-							Dpos::capture_epoch_snapshot(&Dpos::active_validators())
-								.validators
-								.get(&active_validator),
+							Dpos::capture_epoch_snapshot(
+								EpochIndex::<Test>::get(),
+								&Dpos::active_validators()
+							)
+							.validators
+							.get(active_validator),
 							Some(bond)
 						);
 						// Calculate the rewards of the validator in every epoch
-						epoch_rewards[indx] += Dpos::calculate_reward(
-							*bond,
-							<mock::Test as pallet::Config>::AuthorCommission::get(),
-						);
+						epoch_rewards[indx] += Dpos::block_reward(*bond);
 					}
 					// If the epoch ends...
 					if round % TEST_BLOCKS_PER_EPOCH == 0 {
 						// Check if the reward points are calculated correctly
-						assert_eq!(Dpos::reward_points(active_validator), epoch_rewards[indx]);
+						assert_eq!(Dpos::reward_points(*active_validator), epoch_rewards[indx]);
 					}
 				}
 				ext.next_block();
@@ -190,28 +193,16 @@ fn should_ok_only_top_validators_can_produce_and_receive_rewards() {
 			ext.run_to_block(4);
 
 			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), 0);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300));
 			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), 0);
 
 			ext.next_block(); // block 5
 			ext.next_block(); // block 6 - new epoch
 
 			assert_eq!(Dpos::active_validators().len(), 3);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200));
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300));
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400));
 			test_helpers::register_new_candidate(CANDIDATE_4.id, CANDIDATE_4.balance, 500);
 			test_helpers::register_new_candidate(CANDIDATE_5.id, CANDIDATE_5.balance, 600);
 			test_helpers::register_new_candidate(CANDIDATE_6.id, CANDIDATE_6.balance, 700);
@@ -219,18 +210,9 @@ fn should_ok_only_top_validators_can_produce_and_receive_rewards() {
 			ext.next_block(); // block 7
 			ext.next_block(); // block 8
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400));
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
@@ -239,18 +221,9 @@ fn should_ok_only_top_validators_can_produce_and_receive_rewards() {
 			assert_eq!(Dpos::active_validators().len(), 3);
 
 			// Old validator set will stop producing blocks and receive reward from this epoch
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
 			// New validator set will replace because they have top delegations
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
 			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), 0);
@@ -259,102 +232,39 @@ fn should_ok_only_top_validators_can_produce_and_receive_rewards() {
 			ext.next_block(); // block 10
 
 			assert_eq!(Dpos::active_validators().len(), 3);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
 			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), 0);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
 
 			ext.next_block(); // block 11
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
 			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), 0);
 
 			ext.next_block(); // block 12
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			); // Author
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_6.id),
-				Dpos::calculate_reward(700, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2); // Author
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600));
+			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), Dpos::block_reward(700));
 
 			ext.next_block(); // block 13
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission() * 2)
-			); // Author
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(300, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_3.id),
-				Dpos::calculate_reward(400, test_helpers::get_author_commission() * 2)
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_4.id),
-				Dpos::calculate_reward(500, test_helpers::get_author_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_5.id),
-				Dpos::calculate_reward(600, test_helpers::get_author_commission()) * 2
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_6.id),
-				Dpos::calculate_reward(700, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2); // Author
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_3.id), Dpos::block_reward(400) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_4.id), Dpos::block_reward(500));
+			assert_eq!(Dpos::reward_points(CANDIDATE_5.id), Dpos::block_reward(600) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_6.id), Dpos::block_reward(700));
 		});
 }
 
@@ -396,11 +306,12 @@ fn should_ok_reward_distributed_for_validators_and_delegators() {
 			assert_eq!(
 				Dpos::last_epoch_snapshot(),
 				Some(EpochSnapshot {
-					delegations: BTreeMap::default(),
-					validators: BTreeMap::from_iter(vec![
+					total_delegations: BoundedBTreeMap::new(),
+					validators: BoundedBTreeMap::try_from(BTreeMap::from_iter(vec![
 						(CANDIDATE_1.id, 200),
 						(CANDIDATE_2.id, 100)
-					])
+					]))
+					.unwrap()
 				})
 			);
 
@@ -409,19 +320,13 @@ fn should_ok_reward_distributed_for_validators_and_delegators() {
 			assert!(System::block_number() == 4);
 
 			// Because the FindAuthor we are using is round robin, so candidate 2 is a block author
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200));
 
 			ext.next_block();
 			assert!(System::block_number() == 5);
 
 			// Now candidate 2 will be the block author and receive reward
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(100, test_helpers::get_author_commission())
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(100));
 
 			// At this block height, ACCOUNT 6 delegate to the candidate 1
 			// (Reward points are distributed in the next epoch 9)
@@ -433,43 +338,36 @@ fn should_ok_reward_distributed_for_validators_and_delegators() {
 			assert_eq!(
 				Dpos::last_epoch_snapshot(),
 				Some(EpochSnapshot {
-					delegations: BTreeMap::from_iter(vec![((ACCOUNT_6.id, CANDIDATE_1.id), 300)]),
-					validators: BTreeMap::from_iter(vec![
+					total_delegations: BoundedBTreeMap::try_from(BTreeMap::from_iter(vec![(
+						CANDIDATE_1.id,
+						300
+					)]))
+					.unwrap(),
+					validators: BoundedBTreeMap::try_from(BTreeMap::from_iter(vec![
 						(CANDIDATE_1.id, 200),
 						(CANDIDATE_2.id, 100)
-					])
+					]))
+					.unwrap()
 				})
 			);
 			// Candidate 1 is the block producer of this block height
 			// Reward points are still updated using the last epoch snapshot
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission()) * 2
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 2);
 			// Deleagor don't receive the reward at this stage yet as it has just been delegated
-			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), Dpos::calculate_reward(300, 0));
+			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
 			assert_eq!(Balances::free_balance(ACCOUNT_6.id), ACCOUNT_6.balance - 300);
 
 			// Now the snapshot is updated
 			ext.next_block();
 			assert!(System::block_number() == 7);
 
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(100, test_helpers::get_author_commission()) * 2
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(100) * 2);
 			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
 
 			ext.next_block();
 			assert!(System::block_number() == 8);
-			assert_eq!(
-				Dpos::reward_points(ACCOUNT_6.id),
-				Dpos::calculate_reward(300, test_helpers::get_delegator_commission())
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission()) * 3
-			);
+			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), Dpos::block_reward(300));
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 3);
 
 			// NEXT EPOCH STARTS: Now the snapshot is updated
 			ext.next_block();
@@ -478,34 +376,21 @@ fn should_ok_reward_distributed_for_validators_and_delegators() {
 			ext.next_block();
 			assert!(System::block_number() == 10);
 
-			assert_eq!(
-				Dpos::reward_points(ACCOUNT_6.id),
-				Dpos::calculate_reward(300, test_helpers::get_delegator_commission()) * 2
-			);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_2.id),
-				Dpos::calculate_reward(100, test_helpers::get_author_commission()) * 3
-			);
+			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), Dpos::block_reward(300) * 2);
+			assert_eq!(Dpos::reward_points(CANDIDATE_2.id), Dpos::block_reward(100) * 3);
 
 			// Account 6 try to claim its reward points
-			assert_eq!(
-				Dpos::reward_points(ACCOUNT_6.id),
-				Dpos::calculate_reward(300, test_helpers::get_delegator_commission()) * 2
-			);
+			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), Dpos::block_reward(300) * 2);
 			assert_ok!(Dpos::claim_reward(ros(ACCOUNT_6.id)));
 			assert_eq!(
 				Balances::free_balance(ACCOUNT_6.id),
-				ACCOUNT_6.balance - 300 +
-					Dpos::calculate_reward(300, test_helpers::get_delegator_commission()) * 2
+				ACCOUNT_6.balance - 300 + Dpos::block_reward(300) * 2
 			);
 			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
 
 			ext.next_block();
 			assert!(System::block_number() == 11);
-			assert_eq!(
-				Dpos::reward_points(CANDIDATE_1.id),
-				Dpos::calculate_reward(200, test_helpers::get_author_commission()) * 4
-			);
+			assert_eq!(Dpos::reward_points(CANDIDATE_1.id), Dpos::block_reward(200) * 4);
 			assert_eq!(Dpos::reward_points(ACCOUNT_6.id), 0);
 		});
 }
@@ -526,3 +411,75 @@ fn should_ok_round_robin_style_return_author_in_test() {
 		}
 	});
 }
+
+#[test]
+fn should_ok_custom_election_provider_elect_active_validator_set() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 300),
+		(CANDIDATE_2.id, 200),
+		(CANDIDATE_3.id, 100),
+	])
+	.max_active_validators(2)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::LowestStake)
+	.build()
+	.execute_with(|| {
+		// The custom election provider elects the candidates with the lowest stake
+		let active_validator_set = Dpos::select_active_validator_set();
+		assert_eq!(
+			active_validator_set,
+			vec![(CANDIDATE_3.id, 100, 100), (CANDIDATE_2.id, 200, 200)]
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+		assert_eq!(CurrentActiveValidators::<Test>::get().to_vec(), active_validator_set);
+	});
+}
+
+#[test]
+fn should_ok_keep_previous_set_when_not_enough_online_candidates() {
+	let mut ext = TestExtBuilder::default();
+	ext.min_active_validators(2)
+		.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+		.genesis_candidates(vec![
+			(CANDIDATE_1.id, 300),
+			(CANDIDATE_2.id, 200),
+			(CANDIDATE_3.id, 100),
+		])
+		.build()
+		.execute_with(|| {
+			let previous_active_set = Dpos::active_validators();
+			let previous_snapshot = Dpos::last_epoch_snapshot();
+			assert_eq!(previous_active_set.len(), 3);
+
+			// Two candidates request to leave the pool and turn offline, only one candidate is
+			// online while the pool still has three candidates
+			assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_1.id)));
+			assert_ok!(Dpos::delay_deregister_candidate(ros(CANDIDATE_2.id)));
+			assert_eq!(CandidatePool::<Test>::count(), 3);
+			assert_eq!(Dpos::select_active_validator_set(), vec![]);
+
+			ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+
+			// The last good set is kept instead of installing an empty set
+			assert_eq!(Dpos::active_validators(), previous_active_set);
+			assert_eq!(Dpos::last_epoch_snapshot(), previous_snapshot);
+			assert_ne!(find_author(), None);
+			System::assert_has_event(RuntimeEvent::Dpos(Event::ElectionFailed {
+				epoch: 1,
+				online_candidates: 1,
+				retained_validators: 3,
+			}));
+			assert_eq!(EpochIndex::<Test>::get(), 1);
+
+			// Once the candidates turn back online, the election succeeds again
+			assert_ok!(Dpos::cancel_deregister_candidate_request(ros(CANDIDATE_1.id)));
+			ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+			assert_eq!(
+				Dpos::active_validators().to_vec(),
+				vec![(CANDIDATE_1.id, 300, 300), (CANDIDATE_3.id, 100, 100)]
+			);
+		});
+}
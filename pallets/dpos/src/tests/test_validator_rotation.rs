@@ -0,0 +1,194 @@
+use crate::{mock::*, *};
+use constants::{
+	CANDIDATE_1, CANDIDATE_2, CANDIDATE_3, CANDIDATE_4, CANDIDATE_5, TEST_BLOCKS_PER_EPOCH,
+};
+use tests::test_helpers;
+
+#[test]
+fn should_ok_rotate_runners_up_every_epoch() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::StakeWeightRotation)
+	.rotation_slots(1)
+	.build()
+	.execute_with(|| {
+		// The top two candidates keep their slots, the last slot rotates among the runners-up
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_4.id]
+		);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+			last_epoch: 1,
+			next_epoch: 2,
+			at_block: TEST_BLOCKS_PER_EPOCH * 2,
+			total_candidates: 5,
+			total_validators: 3,
+			replaced_validators: 1,
+			deferred_challengers: 0,
+			rotated_in: vec![CANDIDATE_4.id],
+			rotated_out: vec![CANDIDATE_3.id],
+		}));
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 3);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_5.id]
+		);
+		System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+			last_epoch: 2,
+			next_epoch: 3,
+			at_block: TEST_BLOCKS_PER_EPOCH * 3,
+			total_candidates: 5,
+			total_validators: 3,
+			replaced_validators: 1,
+			deferred_challengers: 0,
+			rotated_in: vec![CANDIDATE_5.id],
+			rotated_out: vec![CANDIDATE_4.id],
+		}));
+
+		// The rotation wraps around the runners-up
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 4);
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+		);
+	});
+}
+
+#[test]
+fn should_ok_no_rotation_event_when_active_set_unchanged() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.max_active_validators(3)
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::StakeWeightRotation)
+	.rotation_slots(0)
+	.build()
+	.execute_with(|| {
+		// Without rotation slots, the election is equivalent to the top staked election
+		let active_validator_set = Dpos::select_active_validator_set();
+		assert_eq!(
+			active_validator_set,
+			TopStakeElection::elect(3, Dpos::get_online_candidate_set(), vec![])
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(Dpos::active_validators().to_vec(), active_validator_set);
+		assert!(!System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::Dpos(Event::NextEpochMoved { rotated_in, rotated_out, .. })
+				if !rotated_in.is_empty() || !rotated_out.is_empty()
+		)));
+	});
+}
+
+#[test]
+fn should_ok_elect_all_candidates_when_not_enough_runners_up() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![
+		(CANDIDATE_1.id, 500),
+		(CANDIDATE_2.id, 400),
+		(CANDIDATE_3.id, 300),
+		(CANDIDATE_4.id, 200),
+		(CANDIDATE_5.id, 100),
+	])
+	.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+	.election_mode(TestElectionMode::StakeWeightRotation)
+	.max_active_validators(5)
+	.rotation_slots(2)
+	.build()
+	.execute_with(|| {
+		assert_eq!(
+			test_helpers::active_validator_ids(),
+			vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id, CANDIDATE_4.id, CANDIDATE_5.id]
+		);
+
+		ext.run_to_block(TEST_BLOCKS_PER_EPOCH * 2);
+		assert_eq!(
+			Dpos::active_validators().to_vec(),
+			TopStakeElection::elect(5, Dpos::get_online_candidate_set(), vec![])
+		);
+	});
+}
+
+#[test]
+fn should_ok_break_stake_ties_by_account_id() {
+	TestExtBuilder::default().build().execute_with(|| {
+		let candidates = vec![
+			(CANDIDATE_3.id, 300, 300),
+			(CANDIDATE_2.id, 300, 300),
+			(CANDIDATE_4.id, 200, 200),
+			(CANDIDATE_1.id, 300, 300),
+		];
+		let mut reversed_candidates = candidates.clone();
+		reversed_candidates.reverse();
+
+		let winners =
+			StakeWeightRotationElection::<Test, RotationSlots>::elect(2, candidates, vec![]);
+		assert_eq!(winners, vec![(CANDIDATE_1.id, 300, 300), (CANDIDATE_2.id, 300, 300)]);
+		assert_eq!(
+			StakeWeightRotationElection::<Test, RotationSlots>::elect(
+				2,
+				reversed_candidates,
+				vec![]
+			),
+			winners
+		);
+	});
+}
+
+#[test]
+fn should_ok_not_count_added_validators_as_replaced_when_set_grows() {
+	let mut ext = TestExtBuilder::default();
+	ext.genesis_candidates(vec![(CANDIDATE_1.id, 500), (CANDIDATE_2.id, 400)])
+		.max_active_validators(3)
+		.epoch_duration(TEST_BLOCKS_PER_EPOCH)
+		.build()
+		.execute_with(|| {
+			test_helpers::register_new_candidate(CANDIDATE_3.id, CANDIDATE_3.balance, 300);
+
+			// The new validator takes a free slot, no validator is replaced
+			ext.run_to_block(TEST_BLOCKS_PER_EPOCH);
+			assert_eq!(
+				test_helpers::active_validator_ids(),
+				vec![CANDIDATE_1.id, CANDIDATE_2.id, CANDIDATE_3.id]
+			);
+			System::assert_has_event(RuntimeEvent::Dpos(Event::NextEpochMoved {
+				last_epoch: 0,
+				next_epoch: 1,
+				at_block: TEST_BLOCKS_PER_EPOCH,
+				total_candidates: 3,
+				total_validators: 3,
+				replaced_validators: 0,
+				deferred_challengers: 0,
+				rotated_in: vec![CANDIDATE_3.id],
+				rotated_out: vec![],
+			}));
+		});
+}
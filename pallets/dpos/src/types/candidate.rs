@@ -1,13 +1,17 @@
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame::deps::frame_support::{
 	sp_runtime::traits::{CheckedAdd, CheckedSub},
-	traits::DefensiveSaturating,
+	traits::{Defensive, DefensiveSaturating, Get},
+	EqNoBound, PartialEqNoBound,
 };
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
-use sp_runtime::traits::Zero;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	BoundedBTreeMap, BoundedVec, Perbill,
+};
 
 use crate::{BalanceOf, Config};
 
@@ -25,11 +29,27 @@ pub struct CandidateDetail<T: Config> {
 	pub bond: BalanceOf<T>,
 	pub total_delegations: BalanceOf<T>,
 	pub status: ValidatorStatus,
+	/// The commission taken by the candidate off the epoch rewards before they are shared with
+	/// its delegators
+	pub commission: Perbill,
+	/// The epoch in which the commission was last updated
+	pub commission_updated_at: Option<u32>,
 }
 
 impl<T: Config> CandidateDetail<T> {
 	pub fn new(bond: BalanceOf<T>) -> Self {
-		CandidateDetail { total_delegations: Zero::zero(), bond, status: ValidatorStatus::Online }
+		CandidateDetail {
+			total_delegations: Zero::zero(),
+			bond,
+			status: ValidatorStatus::Online,
+			commission: Perbill::zero(),
+			commission_updated_at: None,
+		}
+	}
+
+	pub fn update_commission(&mut self, commission: Perbill, epoch_index: u32) {
+		self.commission = commission;
+		self.commission_updated_at = Some(epoch_index);
 	}
 
 	pub fn add_delegated_amount(
@@ -64,29 +84,75 @@ impl<T: Config> CandidateDetail<T> {
 	}
 }
 
-#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
+/// The maximum number of delegations captured in an epoch snapshot, which is
+/// `MaxActiveValidators * MaxCandidateDelegators`
+pub struct MaxSnapshotDelegations<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxSnapshotDelegations<T> {
+	fn get() -> u32 {
+		T::MaxActiveValidators::get().saturating_mul(T::MaxCandidateDelegators::get())
+	}
+}
+
+/// The active validators of an epoch with their bonds and the total delegated to them. The
+/// delegations of the snapshot are stored in `EpochDelegations`, keyed by the delegator
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEqNoBound, EqNoBound)]
 #[scale_info(skip_type_params(T))]
 pub struct EpochSnapshot<T: Config> {
-	pub validators: BTreeMap<T::AccountId, BalanceOf<T>>,
-	pub delegations: BTreeMap<(T::AccountId, T::AccountId), BalanceOf<T>>,
+	pub validators: BoundedBTreeMap<T::AccountId, BalanceOf<T>, T::MaxActiveValidators>,
+	pub total_delegations: BoundedBTreeMap<T::AccountId, BalanceOf<T>, T::MaxActiveValidators>,
+}
+
+impl<T: Config> Default for EpochSnapshot<T> {
+	fn default() -> Self {
+		Self { validators: BoundedBTreeMap::new(), total_delegations: BoundedBTreeMap::new() }
+	}
 }
 
 impl<T: Config> EpochSnapshot<T> {
-	pub fn default() -> Self {
-		Self { validators: BTreeMap::default(), delegations: BTreeMap::default() }
+	/// Adds the delegated amount to the total delegations of the validator
+	pub fn add_delegation(&mut self, candidate: T::AccountId, amount: BalanceOf<T>) {
+		let total = self.total_delegations_of(&candidate).saturating_add(amount);
+		let _ = self
+			.total_delegations
+			.try_insert(candidate, total)
+			.defensive_proof("Validators are bounded by the size of the active validator set");
 	}
 
-	pub fn add_delegator(
-		self: &mut Self,
-		delegator: T::AccountId,
-		candidate: T::AccountId,
-		amount: BalanceOf<T>,
-	) {
-		self.delegations.insert((delegator, candidate), amount);
+	/// The total delegated to the validator in the snapshot
+	pub fn total_delegations_of(&self, candidate: &T::AccountId) -> BalanceOf<T> {
+		self.total_delegations.get(candidate).copied().unwrap_or_default()
+	}
+
+	/// The total bonds and delegations captured in the snapshot
+	pub fn total_staked(&self) -> BalanceOf<T> {
+		self.validators
+			.values()
+			.chain(self.total_delegations.values())
+			.fold(Zero::zero(), |total: BalanceOf<T>, amount| total.saturating_add(*amount))
 	}
 
-	pub fn add_validator(self: &mut Self, candidate: T::AccountId, amount: BalanceOf<T>) {
-		self.validators.insert(candidate, amount);
+	pub fn add_validator(&mut self, candidate: T::AccountId, amount: BalanceOf<T>) {
+		let _ = self
+			.validators
+			.try_insert(candidate, amount)
+			.defensive_proof("Validators are bounded by the size of the active validator set");
+	}
+
+	/// Adds the validators and the delegations of a page captured during the paged election
+	pub fn append_page(&mut self, page: EpochSnapshot<T>) {
+		for (validator, bond) in page.validators {
+			self.add_validator(validator, bond);
+		}
+		for (validator, amount) in page.total_delegations {
+			self.add_delegation(validator, amount);
+		}
+	}
+
+	/// Removes the validators that are not in the provided set along with their delegations
+	pub fn retain_validators(&mut self, validators: &BTreeSet<T::AccountId>) {
+		self.validators.retain(|validator, _| validators.contains(validator));
+		self.total_delegations.retain(|validator, _| validators.contains(validator));
 	}
 }
 
@@ -94,6 +160,10 @@ impl<T: Config> EpochSnapshot<T> {
 pub type CandidateDelegationSet<T: Config> =
 	sp_std::vec::Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)>;
 
+#[allow(type_alias_bounds)]
+pub type BoundedCandidateDelegationSet<T: Config> =
+	BoundedVec<(T::AccountId, BalanceOf<T>, BalanceOf<T>), <T as Config>::MaxActiveValidators>;
+
 #[allow(type_alias_bounds)]
 pub type ActiveValidatorSet<T: Config> = sp_std::vec::Vec<(T::AccountId, BalanceOf<T>)>;
 
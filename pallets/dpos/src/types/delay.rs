@@ -1,4 +1,4 @@
-use crate::{BalanceOf, Config};
+use crate::{BalanceOf, Config, MomentOf};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame::deps::frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
@@ -17,6 +17,8 @@ pub struct DelayActionRequest<T: Config> {
 	// The block number where the request is created
 	pub created_at: BlockNumberFor<T>,
 	pub delay_for: BlockNumberFor<T>,
+	// The timestamp where the request is created, only set when the epochs are moved by timestamp
+	pub created_at_time: Option<MomentOf<T>>,
 	pub amount: Option<BalanceOf<T>>,
 	pub target: Option<T::AccountId>,
 }
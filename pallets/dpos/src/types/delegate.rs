@@ -19,3 +19,7 @@ impl<T: Config> DelegationInfo<T> {
 		self.amount = amount;
 	}
 }
+
+/// Delegations in the form of `(delegator, candidate, amount)`
+#[allow(type_alias_bounds)]
+pub type DelegationEdges<T: Config> = sp_std::vec::Vec<(T::AccountId, T::AccountId, BalanceOf<T>)>;
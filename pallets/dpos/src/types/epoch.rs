@@ -0,0 +1,88 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame::deps::frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_runtime::{BoundedVec, Saturating};
+
+use crate::{BalanceOf, Config};
+
+/// The mechanism that moves the pallet to the next epoch
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, Copy)]
+pub enum EpochTrigger {
+	/// Standalone mode, the epoch moves in `on_initialize` when the epoch duration has elapsed
+	BlockNumber,
+	/// The epoch moves with the sessions of `pallet_session`, which receives the elected
+	/// validator set through the `SessionManager` implementation of the pallet
+	Session,
+	/// The epoch moves in `on_initialize` when the wall-clock duration of the epoch has elapsed
+	/// according to `pallet_timestamp`. The delays of the delay action requests are measured in
+	/// time as well
+	Timestamp,
+}
+
+/// Mode of the epoch forcing, honoured by the epoch logic of `on_initialize`
+#[derive(
+	Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, Copy, Default,
+)]
+pub enum Forcing {
+	/// The epochs move normally when the epoch duration has elapsed
+	#[default]
+	NotForcing,
+	/// A new epoch starts at the next block, the mode is then reset to `NotForcing`
+	ForceNew,
+	/// The epochs do not move, the active validator set is frozen
+	ForceNone,
+	/// A new epoch starts at every block
+	ForceAlways,
+}
+
+/// Progress of the paged election of the next epoch, each phase is processed page by page
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, Copy)]
+pub enum ElectionPhase {
+	/// The electable candidates are read from the sorted candidate index. `bag` is the index of
+	/// the bag counted from the highest threshold and `position` the next candidate in the bag
+	Candidates { bag: u32, position: u32 },
+	/// The delegations of the electable candidates are read, `cursor` is the index of the next
+	/// candidate in `ElectableCandidates`
+	Delegations { cursor: u32 },
+	/// The electable candidates and their delegations are read, the election runs in the next
+	/// page
+	Elect,
+}
+
+/// Record of an epoch, kept for the last `HistoryDepth` epochs
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
+#[scale_info(skip_type_params(T))]
+pub struct EpochInfo<T: Config> {
+	/// The block at which the epoch started
+	pub start_block: BlockNumberFor<T>,
+	/// The block at which the epoch is planned to end when it started
+	pub planned_end_block: BlockNumberFor<T>,
+	/// The active validator set of the epoch
+	pub validators: BoundedVec<T::AccountId, <T as Config>::MaxActiveValidators>,
+	/// The total stake (bond + total delegations) of the active validator set
+	pub total_active_stake: BalanceOf<T>,
+	/// The total rewards issued to the validators and their delegators during the epoch
+	pub total_rewards: BalanceOf<T>,
+}
+
+impl<T: Config> EpochInfo<T> {
+	pub fn new(
+		start_block: BlockNumberFor<T>,
+		planned_end_block: BlockNumberFor<T>,
+		validators: BoundedVec<T::AccountId, <T as Config>::MaxActiveValidators>,
+		total_active_stake: BalanceOf<T>,
+	) -> Self {
+		EpochInfo {
+			start_block,
+			planned_end_block,
+			validators,
+			total_active_stake,
+			total_rewards: Default::default(),
+		}
+	}
+
+	pub fn add_rewards(&mut self, rewards: BalanceOf<T>) {
+		self.total_rewards = self.total_rewards.saturating_add(rewards);
+	}
+}
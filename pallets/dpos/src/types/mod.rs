@@ -8,4 +8,10 @@ pub use delay::*;
 pub mod delegate;
 pub use delegate::*;
 
+pub mod epoch;
+pub use epoch::*;
+
+pub mod reward;
+pub use reward::*;
+
 pub type DispatchResultWithValue<T> = Result<T, sp_runtime::DispatchError>;
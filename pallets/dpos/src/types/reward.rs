@@ -0,0 +1,246 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame::deps::frame_support::{traits::Defensive, DefaultNoBound, EqNoBound, PartialEqNoBound};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One, Saturating, Zero},
+	BoundedBTreeMap, FixedPointNumber, FixedU128, Perbill,
+};
+
+use crate::{BalanceOf, Config};
+
+/// An unclaimed reward of a delegator in a reward pool, as `(epoch index, validator, reward per
+/// delegated unit, rewards)`
+pub type DelegatorRewardClaim<T> =
+	(u32, <T as frame::deps::frame_system::Config>::AccountId, FixedU128, BalanceOf<T>);
+
+/// The model used to reward the block authors and their delegators
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, Copy)]
+pub enum RewardModel {
+	/// The rewards of every block are calculated from the stake of the block author with the
+	/// balance rate. The validator takes its commission off the rewards, and the rest is credited
+	/// to the validator and the reward pool of its delegators pro-rata to the stake
+	BlockRewards,
+	/// The validators earn points for every block authored in the epoch. When the epoch ends,
+	/// the epoch reward is split between the validators by points and paid out with
+	/// `payout_epoch`
+	EpochPoints,
+}
+
+/// The source from which the claimed rewards are paid
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone)]
+pub enum RewardSource<AccountId> {
+	/// The rewards are minted into the account of the claimer
+	Mint,
+	/// The rewards are transferred from the reward pot of the pallet, the account derived from
+	/// `PalletId`, which is filled with `top_up_reward_pot`
+	Pot,
+	/// The rewards are transferred from the treasury account
+	Treasury(AccountId),
+}
+
+/// The destination of the rewards forfeited when they are not claimed before they expire
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone)]
+pub enum ExpiredRewardDestination<AccountId> {
+	/// The rewards are never paid. They are not minted with [`RewardSource::Mint`], and are
+	/// burnt from the reward pot or the treasury account otherwise
+	Burn,
+	/// The rewards are paid from the reward source into the treasury account
+	Treasury(AccountId),
+}
+
+/// The destination of the rewards claimed by an account or paid out on its behalf
+#[derive(
+	Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, DefaultNoBound,
+)]
+pub enum RewardDestination<AccountId> {
+	/// The rewards are paid into the free balance of the account
+	#[default]
+	Free,
+	/// The rewards are added to the candidate bond of the account, or to its largest delegation
+	/// when the account is not a candidate
+	Staked,
+	/// The rewards are paid into the free balance of another account
+	Account(AccountId),
+}
+
+/// Number of milliseconds in a year of 365.25 days
+pub const MILLISECS_PER_YEAR: u64 = 31_557_600_000;
+
+/// `ln 2` in the precision of `FixedU128`
+const LN_2: u128 = 693_147_180_559_945_309;
+
+/// NPoS style inflation curve, the annual inflation depends on the ratio of the total issuance
+/// that is staked. The inflation increases linearly from `min_inflation` when nothing is staked
+/// to `max_inflation` at the `ideal_stake`, then decreases towards `min_inflation`, halving
+/// every `falloff` above the `ideal_stake`
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, Copy)]
+pub struct InflationCurve {
+	/// The staking ratio at which the inflation is the highest
+	pub ideal_stake: Perbill,
+	/// The annual inflation when nothing is staked, or when everything is staked
+	pub min_inflation: Perbill,
+	/// The annual inflation at the ideal staking ratio
+	pub max_inflation: Perbill,
+	/// The staking ratio above the ideal stake over which the inflation above the minimum halves
+	pub falloff: Perbill,
+}
+
+impl InflationCurve {
+	pub fn is_valid(&self) -> bool {
+		!self.ideal_stake.is_zero() &&
+			!self.falloff.is_zero() &&
+			self.min_inflation <= self.max_inflation
+	}
+
+	/// The annual inflation at the staking ratio
+	pub fn annual_inflation(&self, stake_ratio: Perbill) -> Perbill {
+		let max_above_min = self.max_inflation.saturating_sub(self.min_inflation);
+		let factor = if stake_ratio <= self.ideal_stake {
+			Perbill::from_rational(stake_ratio.deconstruct(), self.ideal_stake.deconstruct())
+		} else {
+			Self::exp2_neg(FixedU128::saturating_from_rational(
+				stake_ratio.saturating_sub(self.ideal_stake).deconstruct(),
+				self.falloff.deconstruct(),
+			))
+		};
+		self.min_inflation.saturating_add(factor * max_above_min)
+	}
+
+	/// Computes `2^-exponent`. The integer part of the exponent halves the result, the fractional
+	/// part is computed with the Taylor series of `e^(-x ln 2)`, summed until its terms vanish in
+	/// the precision of `FixedU128`, far below the precision of `Perbill`
+	fn exp2_neg(exponent: FixedU128) -> Perbill {
+		let halvings = exponent.into_inner() / FixedU128::DIV;
+		if halvings >= 32 {
+			return Perbill::zero();
+		}
+		let x = exponent.frac().saturating_mul(FixedU128::from_inner(LN_2));
+		// The terms alternate in sign, the even terms are added and the odd terms subtracted
+		let (mut even_terms, mut odd_terms) = (FixedU128::one(), FixedU128::zero());
+		let mut term = FixedU128::one();
+		let mut n = 1u128;
+		while !term.is_zero() {
+			term = FixedU128::from_inner(term.saturating_mul(x).into_inner() / n);
+			if n.is_multiple_of(2) {
+				even_terms = even_terms.saturating_add(term);
+			} else {
+				odd_terms = odd_terms.saturating_add(term);
+			}
+			n.saturating_inc();
+		}
+		let approximation = even_terms.saturating_sub(odd_terms);
+		Perbill::from_rational(approximation.into_inner() >> halvings, FixedU128::DIV)
+	}
+
+	/// The reward budget of an epoch lasting `epoch_time` milliseconds
+	pub fn epoch_budget<Balance>(
+		&self,
+		total_staked: Balance,
+		total_issuance: Balance,
+		epoch_time: u64,
+	) -> Balance
+	where
+		Balance: AtLeast32BitUnsigned + Copy,
+	{
+		let stake_ratio = Perbill::from_rational(total_staked, total_issuance);
+		let epoch_inflation = Perbill::from_rational(epoch_time, MILLISECS_PER_YEAR) *
+			self.annual_inflation(stake_ratio);
+		epoch_inflation * total_issuance
+	}
+}
+
+/// The points earned by the validators for the blocks authored in an epoch
+#[derive(
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEqNoBound,
+	EqNoBound,
+	DefaultNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct EpochPoints<T: Config> {
+	/// The total points earned by all the validators
+	pub total: u32,
+	/// The points earned by each validator
+	pub individual: BoundedBTreeMap<T::AccountId, u32, T::MaxActiveValidators>,
+}
+
+impl<T: Config> EpochPoints<T> {
+	pub fn add_point(&mut self, validator: &T::AccountId) {
+		if let Some(points) = self.individual.get_mut(validator) {
+			*points = points.saturating_add(1);
+		} else if self
+			.individual
+			.try_insert(validator.clone(), 1)
+			.defensive_proof("Validators are bounded by the size of the active validator set")
+			.is_err()
+		{
+			return;
+		}
+		self.total = self.total.saturating_add(1);
+	}
+
+	pub fn points_of(&self, validator: &T::AccountId) -> u32 {
+		self.individual.get(validator).copied().unwrap_or_default()
+	}
+}
+
+/// The reward pool of a validator in an epoch. The rewards of the delegators are accumulated per
+/// delegated unit, so that they are computed when the delegators claim instead of every block
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
+#[scale_info(skip_type_params(T))]
+pub struct ValidatorRewardPool<T: Config> {
+	/// The bond of the validator captured in the epoch snapshot
+	pub bond: BalanceOf<T>,
+	/// The total amount delegated to the validator captured in the epoch snapshot
+	pub total_delegations: BalanceOf<T>,
+	/// The commission of the validator in the epoch
+	pub commission: Perbill,
+	/// The accumulated reward per delegated unit in the epoch
+	pub reward_per_stake: FixedU128,
+}
+
+impl<T: Config> ValidatorRewardPool<T> {
+	pub fn new(bond: BalanceOf<T>, total_delegations: BalanceOf<T>, commission: Perbill) -> Self {
+		ValidatorRewardPool {
+			bond,
+			total_delegations,
+			commission,
+			reward_per_stake: FixedU128::zero(),
+		}
+	}
+
+	/// Accumulates the reward per delegated unit. Returns the rewards accrued by all the
+	/// delegators of the pool
+	pub fn accrue(&mut self, reward_per_stake: FixedU128) -> BalanceOf<T> {
+		let accrued_before = self.reward_per_stake.saturating_mul_int(self.total_delegations);
+		self.reward_per_stake = self.reward_per_stake.saturating_add(reward_per_stake);
+		self.reward_per_stake
+			.saturating_mul_int(self.total_delegations)
+			.saturating_sub(accrued_before)
+	}
+
+	/// Shares the reward of the validator with its delegators. The validator takes its commission
+	/// off the reward, the rest is shared pro-rata to the bond and the delegations. Returns the
+	/// rewards accrued by the delegators, the rest of the reward goes to the validator
+	pub fn share(&mut self, reward: BalanceOf<T>) -> BalanceOf<T> {
+		if self.total_delegations.is_zero() {
+			return Zero::zero();
+		}
+		let shared_reward = reward.saturating_sub(self.commission * reward);
+		let delegations_reward = Perbill::from_rational(
+			self.total_delegations,
+			self.total_delegations.saturating_add(self.bond),
+		) * shared_reward;
+		self.accrue(FixedU128::saturating_from_rational(delegations_reward, self.total_delegations))
+	}
+
+	/// The rewards of the delegated amount since the reward per delegated unit was `claimed`
+	pub fn rewards_of(&self, amount: BalanceOf<T>, claimed: FixedU128) -> BalanceOf<T> {
+		self.reward_per_stake.saturating_sub(claimed).saturating_mul_int(amount)
+	}
+}
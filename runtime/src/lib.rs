@@ -8,7 +8,10 @@ use frame::{
 			runtime,
 			traits::AsEnsureOriginWithArg,
 			weights::FixedFee,
+			PalletId,
 		},
+		sp_consensus_aura::sr25519::AuthorityId as AuraId,
+		sp_runtime::impl_opaque_keys,
 	},
 	prelude::*,
 	runtime::{
@@ -18,10 +21,13 @@ use frame::{
 		},
 		prelude::*,
 	},
-	traits::{FindAuthor, One},
+	traits::{ConvertInto, One, OpaqueKeys},
 };
 use pallet_transaction_payment::{ConstFeeMultiplier, FeeDetails, Multiplier, RuntimeDispatchInfo};
 
+#[cfg(test)]
+mod tests;
+
 #[runtime_version]
 const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("pba-assignment-template"),
@@ -76,6 +82,18 @@ mod runtime {
 	#[runtime::pallet_index(5)]
 	pub type Dpos = pallet_dpos;
 
+	/// Notes the author of every block, which is rewarded by `pallet_dpos`.
+	#[runtime::pallet_index(6)]
+	pub type Authorship = pallet_authorship;
+
+	/// Rotates the sessions and the session keys of the validators elected by `pallet_dpos`.
+	#[runtime::pallet_index(7)]
+	pub type Session = pallet_session;
+
+	/// Authors the blocks in turns with the session keys of the active validators.
+	#[runtime::pallet_index(8)]
+	pub type Aura = pallet_aura;
+
 	#[runtime::pallet_index(99)]
 	pub type Timestamp = pallet_timestamp;
 }
@@ -129,9 +147,14 @@ impl pallet_transaction_payment::Config for Runtime {
 	type LengthToFee = FixedFee<0, Balance>;
 }
 
-// we don't really need this pallet, but it PJS apps requires :(
+/// The block time of Aura in milliseconds.
+pub const SLOT_DURATION: u64 = 6_000;
+
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
-impl pallet_timestamp::Config for Runtime {}
+impl pallet_timestamp::Config for Runtime {
+	type OnTimestampSet = Aura;
+	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
+}
 
 parameter_types! {
 	pub const AssetDeposit: Balance = 100;
@@ -173,42 +196,69 @@ parameter_types! {
 	pub const MinCandidateBond: u32 = 1_000;
 	pub const MaxActivevalidators: u32 = 100;
 	pub const MinActiveValidators: u32 = 3;
+	pub const MaxElectableCandidates: u32 = 200;
+	pub const MaxValidatorChurn: u32 = 25;
+	pub const ElectionLookahead: u32 = 10;
+	pub const SnapshotPageSize: u32 = 16;
+	pub const BagThresholds: &'static [Balance] = &[
+		1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000, 200_000, 500_000, 1_000_000,
+		2_000_000, 5_000_000, 10_000_000,
+	];
 	pub const MaxDelegateCount : u32 = 30;
 	pub const EpochDuration : u32 = EPOCH_DURATION;
+	pub const EpochTrigger: pallet_dpos::types::EpochTrigger =
+		pallet_dpos::types::EpochTrigger::Session;
+	pub const HistoryDepth: u32 = 28;
 	pub const DelayDeregisterCandidateDuration : u32 = EPOCH_DURATION * 2;
 	pub const DelayUndelegateCandidate : u32 = EPOCH_DURATION;
 	pub const MinDelegateAmount : u128 = 150;
-	pub const ValidatorCommission : u8 = 5;
-	pub const DelegatorCommission : u8 = 3;
+	pub const RewardModel: pallet_dpos::types::RewardModel =
+		pallet_dpos::types::RewardModel::BlockRewards;
+	pub const EpochReward: Balance = 0;
+	pub const RewardSource: pallet_dpos::types::RewardSource<AccountId> =
+		pallet_dpos::types::RewardSource::Mint;
+	pub const DposPalletId: PalletId = PalletId(*b"py/dpos_");
+	pub const MaxPayoutBatch: u32 = 64;
+	pub const RewardExpiryEpochs: u32 = 84;
+	pub const ExpiredRewardDestination: pallet_dpos::types::ExpiredRewardDestination<AccountId> =
+		pallet_dpos::types::ExpiredRewardDestination::Burn;
 }
 
-pub struct RoundRobinAuthor;
-impl FindAuthor<AccountId> for RoundRobinAuthor {
-	fn find_author<'a, I>(_: I) -> Option<AccountId>
-	where
-		I: 'a + IntoIterator<Item = ([u8; 4], &'a [u8])>,
-	{
-		let active_validator_ids = ValidatorSet::get();
-		if active_validator_ids.len() == 0 {
-			return None;
-		}
-		active_validator_ids
-			.get((System::block_number() % (active_validator_ids.len() as u32)) as usize)
-			.cloned()
+impl_opaque_keys! {
+	pub struct SessionKeys {
+		pub aura: Aura,
 	}
 }
 
 parameter_types! {
-	// This is a temporary storage that will keep the validators. In reality, this would have been
-	// `pallet-aura` or another pallet that would consume these.
-	pub storage ValidatorSet: Vec<AccountId> = vec![];
+	pub const SessionOffset: u32 = 0;
 }
 
-pub struct StoreNewValidatorSet;
-impl pallet_dpos::ReportNewValidatorSet<AccountId> for StoreNewValidatorSet {
-	fn report_new_validator_set(new_set: Vec<AccountId>) {
-		ValidatorSet::set(&new_set);
-	}
+// Every session is an epoch of `pallet_dpos`, the validator set of the next session is elected by
+// `pallet_dpos`.
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<EpochDuration, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<EpochDuration, SessionOffset>;
+	type SessionManager = Dpos;
+	type SessionHandler = <SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_aura::Config for Runtime {
+	type AuthorityId = AuraId;
+	type DisabledValidators = ();
+	type MaxAuthorities = MaxActivevalidators;
+	type AllowMultipleBlocksPerSlot = ConstBool<false>;
+	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
+}
+
+impl pallet_authorship::Config for Runtime {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type EventHandler = Dpos;
 }
 
 pub struct OnSlashHandler;
@@ -223,8 +273,14 @@ impl pallet_dpos::Config for Runtime {
 	type MaxCandidates = MaxCandidates;
 	type MaxCandidateDelegators = MaxCandidateDelegators;
 	type MaxActiveValidators = MaxActivevalidators;
+	type BagThresholds = BagThresholds;
+	type MaxElectableCandidates = MaxElectableCandidates;
+	type MaxValidatorChurn = MaxValidatorChurn;
 	type MinActiveValidators = MinActiveValidators;
-	type ReportNewValidatorSet = StoreNewValidatorSet;
+	type ElectionLookahead = ElectionLookahead;
+	type SnapshotPageSize = SnapshotPageSize;
+	type ElectionProvider = pallet_dpos::TopStakeElection;
+	type ReportNewValidatorSet = ();
 	type WeightInfo = ();
 	type OnSlashHandler = OnSlashHandler;
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -232,11 +288,24 @@ impl pallet_dpos::Config for Runtime {
 	type DelayDeregisterCandidateDuration = DelayDeregisterCandidateDuration;
 	type DelayUndelegateCandidate = DelayUndelegateCandidate;
 	type EpochDuration = EpochDuration;
+	type EpochTrigger = EpochTrigger;
+	type Time = Timestamp;
+	type ExpectedBlockTime = ConstU64<SLOT_DURATION>;
+	type HistoryDepth = HistoryDepth;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ValidatorRegistration = Session;
 	type MinCandidateBond = MinCandidateBond;
 	type MinDelegateAmount = MinDelegateAmount;
-	type AuthorCommission = ValidatorCommission;
-	type DelegatorCommission = DelegatorCommission;
-	type FindAuthor = RoundRobinAuthor;
+	type RewardModel = RewardModel;
+	type EpochReward = EpochReward;
+	type RewardSource = RewardSource;
+	type PalletId = DposPalletId;
+	type MaxPayoutBatch = MaxPayoutBatch;
+	type RewardExpiryEpochs = RewardExpiryEpochs;
+	type ExpiredRewardDestination = ExpiredRewardDestination;
+	// The block author is rewarded through `pallet_authorship`
+	type FindAuthor = ();
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type ConfigControllerOrigin = EnsureRoot<AccountId>;
 }
@@ -262,8 +331,23 @@ type Nonce = <Runtime as frame_system::Config>::Nonce;
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
 type Header = HeaderFor<Runtime>;
 
-type RuntimeExecutive =
-	Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+/// The storage migrations that are run when the runtime is upgraded.
+type Migrations = (
+	pallet_dpos::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_dpos::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_dpos::migrations::v3::MigrateV2ToV3<Runtime>,
+	pallet_dpos::migrations::v4::MigrateV3ToV4<Runtime>,
+	pallet_dpos::migrations::v5::MigrateV4ToV5<Runtime>,
+);
+
+type RuntimeExecutive = Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	Migrations,
+>;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
@@ -344,14 +428,24 @@ impl_runtime_apis! {
 	}
 
 	impl apis::SessionKeys<Block> for Runtime {
-		fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
-			Default::default()
+		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+			SessionKeys::generate(seed)
 		}
 
 		fn decode_session_keys(
-			_encoded: Vec<u8>,
+			encoded: Vec<u8>,
 		) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-			Default::default()
+			SessionKeys::decode_into_raw_public_keys(&encoded)
+		}
+	}
+
+	impl apis::AuraApi<Block, AuraId> for Runtime {
+		fn slot_duration() -> apis::SlotDuration {
+			apis::SlotDuration::from_millis(Aura::slot_duration())
+		}
+
+		fn authorities() -> Vec<AuraId> {
+			pallet_aura::Authorities::<Runtime>::get().into_inner()
 		}
 	}
 
@@ -361,6 +455,14 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_dpos_runtime_api::DposApi<Block, AccountId, Balance> for Runtime {
+		fn preview_next_validator_set(
+		) -> pallet_dpos_runtime_api::ValidatorSetPreview<AccountId, Balance> {
+			let (winners, losers) = Dpos::preview_next_validator_set();
+			pallet_dpos_runtime_api::ValidatorSetPreview { winners, losers }
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -0,0 +1,128 @@
+use super::*;
+use frame::deps::{
+	codec::Encode,
+	frame_support::assert_ok,
+	sp_consensus_aura::{Slot, AURA_ENGINE_ID},
+	sp_core::sr25519,
+	sp_io::TestExternalities,
+	sp_runtime::{traits::Header as _, BuildStorage, Digest, DigestItem},
+};
+
+const INITIAL_BALANCE: Balance = 1_000_000;
+const GENESIS_BOND: Balance = 10_000;
+
+fn account(seed: u8) -> AccountId {
+	AccountId::new([seed; 32])
+}
+
+fn session_keys(seed: u8) -> SessionKeys {
+	SessionKeys { aura: AuraId::from(sr25519::Public::from_raw([seed; 32])) }
+}
+
+fn sorted(mut accounts: Vec<AccountId>) -> Vec<AccountId> {
+	accounts.sort();
+	accounts
+}
+
+fn new_test_ext(validators: &[u8], accounts: &[u8]) -> TestExternalities {
+	RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: accounts.iter().map(|seed| (account(*seed), INITIAL_BALANCE)).collect(),
+		},
+		dpos: pallet_dpos::GenesisConfig {
+			genesis_candidates: validators
+				.iter()
+				.map(|seed| (account(*seed), GENESIS_BOND))
+				.collect(),
+			balance_rate: 1000,
+		},
+		session: pallet_session::GenesisConfig {
+			keys: validators
+				.iter()
+				.map(|seed| (account(*seed), account(*seed), session_keys(*seed)))
+				.collect(),
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+/// Builds the next block in the slot following the slot of the parent block
+fn build_block(parent_header: &Header) -> Header {
+	let slot = Slot::from(u64::from(*parent_header.number()) + 1);
+	let digest = Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())] };
+	let header = Header::new(
+		parent_header.number() + 1,
+		Default::default(),
+		Default::default(),
+		parent_header.hash(),
+		digest,
+	);
+	RuntimeExecutive::initialize_block(&header);
+
+	let set_timestamp = ExtrinsicFor::<Runtime>::new_unsigned(RuntimeCall::Timestamp(
+		pallet_timestamp::Call::set { now: u64::from(slot) * SLOT_DURATION },
+	));
+	assert!(RuntimeExecutive::apply_extrinsic(set_timestamp).unwrap().is_ok());
+	RuntimeExecutive::finalize_block()
+}
+
+#[test]
+fn should_ok_elect_genesis_authorities_with_dpos() {
+	new_test_ext(&[1, 2, 3], &[1, 2, 3]).execute_with(|| {
+		let genesis_validators = vec![account(1), account(2), account(3)];
+		assert_eq!(sorted(Session::validators()), genesis_validators);
+		assert_eq!(
+			sorted(Dpos::active_validators().into_iter().map(|(id, _, _)| id).collect()),
+			genesis_validators
+		);
+		assert_eq!(pallet_aura::Authorities::<Runtime>::get().len(), genesis_validators.len());
+	});
+}
+
+#[test]
+fn should_ok_rotate_authorities_across_epoch_change() {
+	new_test_ext(&[1, 2, 3], &[1, 2, 3, 4]).execute_with(|| {
+		// A new candidate with the highest stake sets its session keys and registers
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(account(4)), session_keys(4), vec![]));
+		assert_ok!(Dpos::register_as_candidate(
+			RuntimeOrigin::signed(account(4)),
+			GENESIS_BOND * 2
+		));
+
+		let mut header = Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		// The candidate is elected when the first session ends and the validator set is applied
+		// one session later
+		while header.number < EPOCH_DURATION * 2 - 1 {
+			header = build_block(&header);
+		}
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(pallet_dpos::EpochIndex::<Runtime>::get(), 1);
+		assert!(!Session::validators().contains(&account(4)));
+		assert!(!pallet_aura::Authorities::<Runtime>::get().contains(&session_keys(4).aura));
+
+		header = build_block(&header);
+		assert_eq!(Session::current_index(), 2);
+		assert_eq!(pallet_dpos::EpochIndex::<Runtime>::get(), 2);
+		assert!(Session::validators().contains(&account(4)));
+		assert!(pallet_aura::Authorities::<Runtime>::get().contains(&session_keys(4).aura));
+		assert!(Dpos::active_validators().iter().any(|(id, _, _)| *id == account(4)));
+
+		// The blocks authored by the active validators are rewarded
+		assert!([1, 2, 3].iter().all(|seed| Dpos::reward_points(account(*seed)) > 0));
+
+		// The new validator authors blocks in the following epoch
+		while header.number < EPOCH_DURATION * 3 {
+			header = build_block(&header);
+		}
+		assert!(Dpos::reward_points(account(4)) > 0);
+	});
+}